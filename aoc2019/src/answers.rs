@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use aoc2019_utils::solution::Part;
+
+/// One puzzle input and the answers it should give. A day can have any number
+/// of these, e.g. one per account.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InputSet {
+    pub day: u32,
+    pub file: String,
+    pub expected: Vec<(Part, String)>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Value {
+    Integer(i64),
+    Text(String),
+}
+
+impl Value {
+    fn into_string(self) -> String {
+        match self {
+            Self::Integer(num) => num.to_string(),
+            Self::Text(txt) => txt,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PartialSet {
+    line_num: usize,
+    keys: Vec<String>,
+    day: Option<u32>,
+    file: Option<String>,
+    expected: Vec<(Part, String)>,
+}
+
+impl PartialSet {
+    fn finish(self) -> Result<InputSet, String> {
+        let err = |key| format!("line {}: input set is missing '{}'", self.line_num, key);
+        Ok(InputSet {
+            day: self.day.ok_or_else(|| err("day"))?,
+            file: self.file.clone().ok_or_else(|| err("file"))?,
+            expected: self.expected,
+        })
+    }
+}
+
+fn parse_string(txt: &str, line_num: usize) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = txt.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            other => {
+                return Err(format!("line {}: bad escape: \\{}",
+                    line_num, other.map(String::from).unwrap_or_default()));
+            },
+        }
+    }
+    Ok(result)
+}
+
+/// Parses the small slice of TOML that answers files use: `[[input]]` tables
+/// holding integers, basic strings and multi-line basic strings, plus
+/// comments.
+pub fn parse_answers(txt: &str) -> Result<Vec<InputSet>, String> {
+    let mut sets = vec![];
+    let mut current: Option<PartialSet> = None;
+    let mut lines = txt.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    while let Some((line_num, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == "[[input]]" {
+            if let Some(set) = current.take() {
+                sets.push(set.finish()?);
+            }
+            current = Some(PartialSet { line_num, ..Default::default() });
+            continue;
+        }
+
+        let (key, val_txt) = match line.find('=') {
+            Some(idx) => (line[..idx].trim(), line[(idx + 1)..].trim()),
+            None => return Err(format!("line {}: expected key = value", line_num)),
+        };
+
+        let val = if let Some(first) = val_txt.strip_prefix("\"\"\"") {
+            let mut raw_lines = vec![];
+            let mut rest = first;
+            loop {
+                if let Some(end) = rest.find("\"\"\"") {
+                    raw_lines.push(&rest[..end]);
+                    break;
+                }
+                raw_lines.push(rest);
+                rest = lines.next().map(|(_, next)| next).ok_or_else(|| {
+                    format!("line {}: unterminated multi-line string", line_num)
+                })?;
+            }
+
+            // Like TOML, a newline right after the opening quotes isn't part
+            // of the string.
+            if raw_lines.len() > 1 && raw_lines[0].is_empty() {
+                raw_lines.remove(0);
+            }
+            Value::Text(parse_string(&raw_lines.join("\n"), line_num)?)
+        } else if val_txt.len() >= 2 && val_txt.starts_with('"') && val_txt.ends_with('"') {
+            Value::Text(parse_string(&val_txt[1..(val_txt.len() - 1)], line_num)?)
+        } else {
+            let num = i64::from_str(val_txt)
+                .map_err(|_| format!("line {}: bad value: {}", line_num, val_txt))?;
+            Value::Integer(num)
+        };
+
+        let set = current.as_mut().ok_or_else(|| {
+            format!("line {}: '{}' is outside of an [[input]] table", line_num, key)
+        })?;
+        if set.keys.iter().any(|seen| seen == key) {
+            return Err(format!("line {}: duplicate key: {}", line_num, key));
+        }
+        set.keys.push(key.to_string());
+
+        match (key, val) {
+            ("day", Value::Integer(day)) if day > 0 => set.day = Some(day as u32),
+            ("file", Value::Text(file)) => set.file = Some(file),
+            ("a", val) => set.expected.push((Part::A, val.into_string())),
+            ("b", val) => set.expected.push((Part::B, val.into_string())),
+            ("day", _) | ("file", _) => {
+                return Err(format!("line {}: bad value for '{}'", line_num, key));
+            },
+            _ => return Err(format!("line {}: unknown key: {}", line_num, key)),
+        }
+    }
+
+    if let Some(set) = current.take() {
+        sets.push(set.finish()?);
+    }
+
+    Ok(sets)
+}
+
+pub fn load_answers(file_name: &str) -> Result<Vec<InputSet>, String> {
+    let txt = std::fs::read_to_string(file_name)
+        .map_err(|err| format!("can't read {}: {}", file_name, err))?;
+    parse_answers(&txt).map_err(|err| format!("{}: {}", file_name, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let txt = concat!(
+            "# a comment\n",
+            "\n",
+            "[[input]]\n",
+            "day = 1\n",
+            "file = \"inputs/day01.txt\"\n",
+            "a = 3320226\n",
+            "b = \"4977473\"\n",
+            "\n",
+            "[[input]]\n",
+            "day = 8\n",
+            "file = \"other/day08.txt\"\n",
+            "b = \"\"\"\n",
+            " #\n",
+            "#  \"\"\"\n",
+        );
+        assert_eq!(parse_answers(txt), Ok(vec![
+            InputSet {
+                day: 1,
+                file: "inputs/day01.txt".to_string(),
+                expected: vec![
+                    (Part::A, "3320226".to_string()),
+                    (Part::B, "4977473".to_string()),
+                ],
+            },
+            InputSet {
+                day: 8,
+                file: "other/day08.txt".to_string(),
+                expected: vec![(Part::B, " #\n#  ".to_string())],
+            },
+        ]));
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert_eq!(parse_answers("day = 1\n"),
+            Err("line 1: 'day' is outside of an [[input]] table".to_string()));
+        assert_eq!(parse_answers("[[input]]\nday = 1\n"),
+            Err("line 1: input set is missing 'file'".to_string()));
+        assert_eq!(parse_answers("[[input]]\nday = x\n"),
+            Err("line 2: bad value: x".to_string()));
+        assert_eq!(parse_answers("[[input]]\nc = 1\n"),
+            Err("line 2: unknown key: c".to_string()));
+        assert_eq!(parse_answers("[[input]]\na = \"\"\"\n12\n"),
+            Err("line 2: unterminated multi-line string".to_string()));
+        assert_eq!(parse_answers("[[input]]\na = 1\nb = 2\na = 3\n"),
+            Err("line 4: duplicate key: a".to_string()));
+    }
+}
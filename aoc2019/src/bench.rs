@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc2019_utils::json::Json;
+use aoc2019_utils::solution::{AnySolution, Part};
+
+const HISTORY_HEADER: &str = "run_id,day,stage,runs,median_us,min_us";
+
+// Stages this quick swing by more than any sensible threshold from run to run,
+// so they're never called regressions.
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part(Part::A), Stage::Part(Part::B)];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "{}", part),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        match txt {
+            "parse" => Ok(Self::Parse),
+            _ => Part::from_str(txt).map(Self::Part),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Timing {
+    pub runs: u32,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            runs: samples.len() as u32,
+            median: samples[samples.len() / 2],
+            min: samples[0],
+        }
+    }
+}
+
+/// One stage of one day, timed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BenchResult {
+    pub day: u32,
+    pub stage: Stage,
+    pub timing: Timing,
+}
+
+impl BenchResult {
+    fn to_csv(self, run_id: u64) -> String {
+        format!("{},{},{},{},{},{}",
+            run_id,
+            self.day,
+            self.stage,
+            self.timing.runs,
+            self.timing.median.as_micros(),
+            self.timing.min.as_micros())
+    }
+
+    fn from_csv(line: &str) -> Result<(u64, Self), String> {
+        let err = || format!("bad history line: {}", line);
+        let fields = line.split(',').map(|field| field.trim()).collect::<Vec<&str>>();
+        if fields.len() != 6 {
+            return Err(err());
+        }
+
+        let run_id = u64::from_str(fields[0]).map_err(|_| err())?;
+        let result = BenchResult {
+            day: u32::from_str(fields[1]).map_err(|_| err())?,
+            stage: Stage::from_str(fields[2]).map_err(|_| err())?,
+            timing: Timing {
+                runs: u32::from_str(fields[3]).map_err(|_| err())?,
+                median: Duration::from_micros(u64::from_str(fields[4]).map_err(|_| err())?),
+                min: Duration::from_micros(u64::from_str(fields[5]).map_err(|_| err())?),
+            },
+        };
+        Ok((run_id, result))
+    }
+
+    pub fn to_json(&self, change: Option<f64>) -> Json {
+        Json::object(vec![
+            ("day", self.day.into()),
+            ("stage", self.stage.to_string().into()),
+            ("runs", self.timing.runs.into()),
+            ("median_us", (self.timing.median.as_micros() as u64).into()),
+            ("min_us", (self.timing.min.as_micros() as u64).into()),
+            ("change", change.map(|change| (change * 1000.0).round() / 1000.0).into()),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BenchOptions {
+    pub day: Option<u32>,
+    pub warmup_runs: u32,
+    pub runs: u32,
+    pub history_file: String,
+    pub save: bool,
+    /// How much slower than last time, as a fraction, counts as a regression.
+    pub threshold: f64,
+    pub json: bool,
+}
+
+fn time_stage<F: FnMut()>(options: &BenchOptions, mut stage: F) -> Timing {
+    (0..options.warmup_runs).for_each(|_| stage());
+    let samples = (0..options.runs)
+        .map(|_| {
+            let start_time = Instant::now();
+            stage();
+            start_time.elapsed()
+        })
+        .collect();
+    Timing::from_samples(samples)
+}
+
+/// Times the parser on the raw input, then each part on input that was
+/// parsed once up front.
+pub fn bench_solution(solution: &dyn AnySolution, input: &str, options: &BenchOptions)
+    -> Vec<BenchResult>
+{
+    let parsed = solution.parse_any(input);
+    Stage::ALL.iter()
+        .map(|stage| {
+            let timing = match stage {
+                Stage::Parse => time_stage(options, || {
+                    black_box(solution.parse_any(black_box(input)));
+                }),
+                Stage::Part(part) => time_stage(options, || {
+                    black_box(solution.solve_parsed(*part, black_box(parsed.as_ref())));
+                }),
+            };
+            BenchResult { day: solution.get_day(), stage: *stage, timing }
+        })
+        .collect()
+}
+
+/// What earlier runs saved to the history file.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct History {
+    /// One more than the highest run id so far, so every run gets its own.
+    pub next_run_id: u64,
+    /// The most recent timing of every day and stage.
+    pub latest: HashMap<(u32, Stage), Timing>,
+}
+
+pub fn load_history(file_name: &str) -> Result<History, String> {
+    let txt = match fs::read_to_string(file_name) {
+        Ok(txt) => txt,
+        Err(_) => return Ok(History::default()),
+    };
+    parse_history(&txt).map_err(|err| format!("{}: {}", file_name, err))
+}
+
+fn parse_history(txt: &str) -> Result<History, String> {
+    let mut latest = HashMap::<(u32, Stage), (u64, Timing)>::new();
+    let mut next_run_id = 0;
+    for line in txt.lines().skip(1).filter(|line| !line.trim().is_empty()) {
+        let (run_id, result) = BenchResult::from_csv(line)?;
+        next_run_id = next_run_id.max(run_id + 1);
+        let entry = latest.entry((result.day, result.stage))
+            .or_insert((run_id, result.timing));
+        if run_id >= entry.0 {
+            *entry = (run_id, result.timing);
+        }
+    }
+
+    Ok(History {
+        next_run_id,
+        latest: latest.into_iter().map(|(key, (_, timing))| (key, timing)).collect(),
+    })
+}
+
+pub fn save_history(file_name: &str, run_id: u64, results: &[BenchResult])
+    -> Result<(), String>
+{
+    let is_new = fs::metadata(file_name).is_err();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .map_err(|err| format!("can't open {}: {}", file_name, err))?;
+
+    let mut txt = String::new();
+    if is_new {
+        txt.push_str(HISTORY_HEADER);
+        txt.push('\n');
+    }
+    results.iter().for_each(|result| {
+        txt.push_str(&result.to_csv(run_id));
+        txt.push('\n');
+    });
+
+    file.write_all(txt.as_bytes())
+        .map_err(|err| format!("can't write {}: {}", file_name, err))
+}
+
+/// The change in median time from `prev` to `timing`, as a fraction.
+pub fn get_change(prev: &Timing, timing: &Timing) -> f64 {
+    let prev = prev.median.as_secs_f64();
+    let now = timing.median.as_secs_f64();
+    if prev == 0.0 {
+        0.0
+    } else {
+        (now - prev) / prev
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 60.0 {
+        aoc2019_utils::sec_to_hrs_mins_secs_str(duration.as_secs())
+    } else if secs >= 1.0 {
+        format!("{:.3} s", secs)
+    } else {
+        format!("{:.3} ms", secs * 1000.0)
+    }
+}
+
+/// Benchmarks the days, prints a table of the results and, unless told not
+/// to, appends them to the history. Returns whether nothing regressed.
+pub fn bench(solutions: &[Box<dyn AnySolution>], options: &BenchOptions) -> Result<bool, String> {
+    let history = load_history(&options.history_file)?;
+
+    if !options.json {
+        println!("day  stage  {:>12}  {:>12}  {:>12}  change",
+            "median", "min", "previous");
+    }
+
+    let mut results = vec![];
+    let mut num_regressions = 0;
+    for solution in solutions {
+        if options.day.is_some_and(|day| day != solution.get_day()) {
+            continue;
+        }
+
+        let file_name = solution.get_input_path();
+        let input = fs::read_to_string(&file_name)
+            .map_err(|err| format!("can't read {}: {}", file_name, err))?;
+        let input = input.trim_end_matches(['\n', '\r']);
+
+        for result in bench_solution(solution.as_ref(), input, options) {
+            let prev = history.latest.get(&(result.day, result.stage));
+            let change = prev.map(|prev| get_change(prev, &result.timing));
+            let is_regression = change.is_some_and(|change| change > options.threshold)
+                && result.timing.median >= MIN_REGRESSION;
+            if is_regression {
+                num_regressions += 1;
+            }
+
+            if options.json {
+                println!("{}", result.to_json(change));
+            } else {
+                println!("{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {}",
+                    result.day,
+                    result.stage.to_string(),
+                    format_duration(result.timing.median),
+                    format_duration(result.timing.min),
+                    prev.map(|prev| format_duration(prev.median)).unwrap_or_default(),
+                    match change {
+                        Some(change) => format!("{:+.1}%{}",
+                            change * 100.0,
+                            if is_regression { "  REGRESSION" } else { "" }),
+                        None => "-".to_string(),
+                    });
+            }
+            results.push(result);
+        }
+    }
+
+    if options.save {
+        save_history(&options.history_file, history.next_run_id, &results)?;
+    }
+
+    if !options.json {
+        println!();
+        println!("{} stages timed, {} regressions (more than {:.0}% slower)",
+            results.len(), num_regressions, options.threshold * 100.0);
+    }
+
+    Ok(num_regressions == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_from_samples() {
+        let samples = [5, 1, 4, 2, 3].iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect();
+        assert_eq!(Timing::from_samples(samples), Timing {
+            runs: 5,
+            median: Duration::from_millis(3),
+            min: Duration::from_millis(1),
+        });
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let result = BenchResult {
+            day: 16,
+            stage: Stage::Part(Part::B),
+            timing: Timing {
+                runs: 5,
+                median: Duration::from_micros(195780),
+                min: Duration::from_micros(190001),
+            },
+        };
+        let line = result.to_csv(1700000000);
+        assert_eq!(line, "1700000000,16,b,5,195780,190001");
+        assert_eq!(BenchResult::from_csv(&line), Ok((1700000000, result)));
+        assert!(BenchResult::from_csv("1,2,c,3,4,5").is_err());
+        assert!(BenchResult::from_csv("1,2,a,3").is_err());
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let history = parse_history(concat!(
+            "run_id,day,stage,runs,median_us,min_us\n",
+            "3,1,a,5,100,90\n",
+            "7,1,a,5,120,110\n",
+            "5,1,a,5,80,70\n",
+            "5,2,parse,5,40,30\n",
+        )).unwrap();
+        assert_eq!(history.next_run_id, 8);
+        assert_eq!(history.latest.len(), 2);
+        assert_eq!(history.latest[&(1, Stage::Part(Part::A))].median, Duration::from_micros(120));
+        assert_eq!(history.latest[&(2, Stage::Parse)].min, Duration::from_micros(30));
+
+        assert_eq!(parse_history(HISTORY_HEADER), Ok(History::default()));
+        assert!(parse_history("run_id\n1,2,c,3,4,5\n").is_err());
+    }
+
+    #[test]
+    fn test_get_change() {
+        let timing = |ms| Timing {
+            runs: 1,
+            median: Duration::from_millis(ms),
+            min: Duration::from_millis(ms),
+        };
+        assert!((get_change(&timing(100), &timing(150)) - 0.5).abs() < 1e-9);
+        assert!((get_change(&timing(100), &timing(50)) + 0.5).abs() < 1e-9);
+        assert_eq!(get_change(&timing(0), &timing(50)), 0.0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.500 s");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1h:02m:05s");
+    }
+}
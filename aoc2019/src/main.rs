@@ -0,0 +1,466 @@
+pub mod answers;
+pub mod bench;
+
+use std::io::Read;
+use std::str::FromStr;
+use std::time::Instant;
+
+use aoc2019_utils::json::Json;
+use bench::BenchOptions;
+use aoc2019_utils::solution::{Answer, AnySolution, Part};
+
+const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+const DEFAULT_HISTORY_FILE: &str = "bench_history.csv";
+
+const USAGE: &str = "\
+usage: aoc2019 run <day|all> [a|b] [--input <file>] [--json]
+       aoc2019 verify [<day>|all] [--answers <file>]
+       aoc2019 bench [<day>|all] [--runs <n>] [--warmup <n>] [--history <file>]
+                     [--threshold <percent>] [--no-save] [--json]
+
+  <day>             the day to run, 1-25
+  all               run every registered day with its default input
+  a|b               run only one part (default: both)
+  --input <file>    read the puzzle input from <file> instead of
+                    inputs/dayNN.txt; use - to read it from stdin
+  --json            print each answer as a line of JSON
+  --answers <file>  check against <file> instead of answers.toml
+  --runs <n>        timed runs of each stage (default: 5)
+  --warmup <n>      untimed runs before timing (default: 1)
+  --history <file>  timing history to compare with and append to
+                    (default: bench_history.csv)
+  --threshold <p>   flag stages more than p percent slower than last time
+                    (default: 10)
+  --no-save         don't append this run to the history";
+
+fn get_solutions() -> Vec<Box<dyn AnySolution>> {
+    vec![
+        Box::new(aoc2019_day01::Day01),
+        Box::new(aoc2019_day02::Day02),
+        Box::new(aoc2019_day03::Day03),
+        Box::new(aoc2019_day04::Day04),
+        Box::new(aoc2019_day05::Day05),
+        Box::new(aoc2019_day06::Day06),
+        Box::new(aoc2019_day07::Day07),
+        Box::new(aoc2019_day08::Day08),
+        Box::new(aoc2019_day09::Day09),
+        Box::new(aoc2019_day10::Day10),
+        Box::new(aoc2019_day11::Day11),
+        Box::new(aoc2019_day12::Day12),
+        Box::new(aoc2019_day13::Day13),
+        Box::new(aoc2019_day14::Day14),
+        Box::new(aoc2019_day15::Day15),
+        Box::new(aoc2019_day16::Day16),
+        Box::new(aoc2019_day17::Day17),
+        Box::new(aoc2019_day18::Day18),
+        Box::new(aoc2019_day19::Day19),
+        Box::new(aoc2019_day20::Day20),
+        Box::new(aoc2019_day21::Day21),
+        Box::new(aoc2019_day22::Day22),
+    ]
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Input {
+    Default,
+    File(String),
+    Stdin,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct RunOptions {
+    day: Option<u32>,
+    parts: Vec<Part>,
+    input: Input,
+    json: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct VerifyOptions {
+    day: Option<u32>,
+    answers_file: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+}
+
+fn parse_day(arg: &str) -> Result<Option<u32>, String> {
+    match arg {
+        "all" => Ok(None),
+        _ => match u32::from_str(arg) {
+            Ok(day) if (1..=25).contains(&day) => Ok(Some(day)),
+            _ => Err(format!("bad day: {} (expected 1-25 or all)", arg)),
+        },
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => Ok(Command::Run(parse_run_args(&args[1..])?)),
+        Some("verify") => Ok(Command::Verify(parse_verify_args(&args[1..])?)),
+        Some("bench") => Ok(Command::Bench(parse_bench_args(&args[1..])?)),
+        Some(cmd) => Err(format!("unknown command: {}", cmd)),
+        None => Err("no command given".to_string()),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunOptions, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next().ok_or("no day given")?)?;
+
+    let mut options = RunOptions {
+        day,
+        parts: Part::ALL.to_vec(),
+        input: Input::Default,
+        json: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                let file_name = args.next().ok_or("--input needs a file name")?;
+                options.input = match file_name.as_str() {
+                    "-" => Input::Stdin,
+                    _ => Input::File(file_name.clone()),
+                };
+            },
+            "--json" => options.json = true,
+            part => options.parts = vec![Part::from_str(part)?],
+        }
+    }
+
+    if options.day.is_none() && options.input != Input::Default {
+        return Err("--input can't be used with all".to_string());
+    }
+
+    Ok(options)
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut options = VerifyOptions {
+        day: None,
+        answers_file: DEFAULT_ANSWERS_FILE.to_string(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let file_name = args.next().ok_or("--answers needs a file name")?;
+                options.answers_file = file_name.clone();
+            },
+            day => options.day = parse_day(day)?,
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_num_arg<T: FromStr>(arg: &str, val: Option<&String>) -> Result<T, String> {
+    let val = val.ok_or_else(|| format!("{} needs a value", arg))?;
+    T::from_str(val).map_err(|_| format!("bad {} value: {}", arg, val))
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions {
+        day: None,
+        warmup_runs: 1,
+        runs: 5,
+        history_file: DEFAULT_HISTORY_FILE.to_string(),
+        save: true,
+        threshold: 0.1,
+        json: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => options.runs = parse_num_arg(arg, args.next())?,
+            "--warmup" => options.warmup_runs = parse_num_arg(arg, args.next())?,
+            "--threshold" => {
+                options.threshold = parse_num_arg::<f64>(arg, args.next())? / 100.0;
+            },
+            "--history" => {
+                let file_name = args.next().ok_or("--history needs a file name")?;
+                options.history_file = file_name.clone();
+            },
+            "--no-save" => options.save = false,
+            "--json" => options.json = true,
+            day => options.day = parse_day(day)?,
+        }
+    }
+
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+
+    Ok(options)
+}
+
+fn read_input(input: &Input, solution: &dyn AnySolution) -> Result<String, String> {
+    let txt = match input {
+        Input::Default => {
+            let file_name = solution.get_input_path();
+            std::fs::read_to_string(&file_name)
+                .map_err(|err| format!("can't read {}: {}", file_name, err))?
+        },
+        Input::File(file_name) => {
+            std::fs::read_to_string(file_name)
+                .map_err(|err| format!("can't read {}: {}", file_name, err))?
+        },
+        Input::Stdin => {
+            let mut txt = String::new();
+            std::io::stdin().read_to_string(&mut txt)
+                .map_err(|err| format!("can't read stdin: {}", err))?;
+            txt
+        },
+    };
+
+    // The parsers expect the input the way it's saved in inputs/, which is
+    // without a trailing newline.
+    Ok(txt.trim_end_matches(['\n', '\r']).to_string())
+}
+
+fn print_answer(day: u32, part: Part, answer: &Answer) {
+    let txt = answer.to_string();
+    if txt.contains('\n') {
+        println!("day {:02} {}:\n{}", day, part, txt.trim_end_matches('\n'));
+    } else {
+        println!("day {:02} {}: {}", day, part, txt);
+    }
+}
+
+fn print_answer_json(day: u32, part: Part, answer: &Answer) {
+    let json = Json::object(vec![
+        ("day", day.into()),
+        ("part", part.to_string().into()),
+        ("answer", answer.to_json()),
+    ]);
+    println!("{}", json);
+}
+
+fn run(options: &RunOptions) -> Result<(), String> {
+    let solutions = get_solutions();
+    let to_run = solutions.iter()
+        .filter(|solution| options.day.is_none_or(|day| day == solution.get_day()))
+        .collect::<Vec<_>>();
+
+    if to_run.is_empty() {
+        return Err(format!("day {} isn't solved yet", options.day.unwrap()));
+    }
+
+    for solution in to_run {
+        let input = read_input(&options.input, solution.as_ref())?;
+        for part in &options.parts {
+            let answer = solution.solve(*part, &input);
+            if options.json {
+                print_answer_json(solution.get_day(), *part, &answer);
+            } else {
+                print_answer(solution.get_day(), *part, &answer);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn same_answer(answer: &Answer, expected: &str) -> bool {
+    answer.to_string().trim_end_matches('\n') == expected.trim_end_matches('\n')
+}
+
+fn print_mismatch(label: &str, txt: &str) {
+    let txt = txt.trim_end_matches('\n');
+    if txt.contains('\n') {
+        println!("    {}:", label);
+        txt.lines().for_each(|line| println!("      {}", line));
+    } else {
+        println!("    {}: {}", label, txt);
+    }
+}
+
+/// Runs every input set in the answers file and prints a pass/fail table.
+/// Returns whether everything passed.
+fn verify(options: &VerifyOptions) -> Result<bool, String> {
+    let input_sets = answers::load_answers(&options.answers_file)?
+        .into_iter()
+        .filter(|set| options.day.is_none_or(|day| day == set.day))
+        .collect::<Vec<_>>();
+    let solutions = get_solutions();
+
+    let file_width = input_sets.iter()
+        .map(|set| set.file.len())
+        .max()
+        .unwrap_or(0)
+        .max("input".len());
+
+    println!("day  part  {:<width$}  result  {:>12}", "input", "time", width = file_width);
+
+    let mut num_passed = 0;
+    let mut num_failed = 0;
+    for set in &input_sets {
+        let solution = solutions.iter().find(|solution| solution.get_day() == set.day);
+        let input = match solution {
+            Some(solution) => read_input(&Input::File(set.file.clone()), solution.as_ref()),
+            None => Err(format!("day {} isn't solved yet", set.day)),
+        };
+
+        for (part, expected) in &set.expected {
+            print!("{:>3}  {:<4}  {:<width$}  ",
+                set.day, part.to_string(), set.file, width = file_width);
+
+            let (solution, input) = match (solution, &input) {
+                (Some(solution), Ok(input)) => (solution, input),
+                (_, Err(err)) => {
+                    println!("error");
+                    println!("    {}", err);
+                    num_failed += 1;
+                    continue;
+                },
+                (None, _) => unreachable!(),
+            };
+
+            let start_time = Instant::now();
+            let answer = solution.solve(*part, input);
+            let elapsed_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+
+            if same_answer(&answer, expected) {
+                println!("pass    {:>9.2} ms", elapsed_ms);
+                num_passed += 1;
+            } else {
+                println!("FAIL    {:>9.2} ms", elapsed_ms);
+                print_mismatch("expected", expected);
+                print_mismatch("got", &answer.to_string());
+                num_failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed", num_passed, num_failed);
+    Ok(num_failed == 0)
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let result = parse_args(&args).and_then(|cmd| match cmd {
+        Command::Run(options) => run(&options).map(|_| true),
+        Command::Verify(options) => verify(&options),
+        Command::Bench(options) => bench::bench(&get_solutions(), &options),
+    });
+
+    match result {
+        Ok(true) => {},
+        Ok(false) => std::process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            std::process::exit(1);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(txt: &str) -> Vec<String> {
+        txt.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&to_args("run 14 b")), Ok(Command::Run(RunOptions {
+            day: Some(14),
+            parts: vec![Part::B],
+            input: Input::Default,
+            json: false,
+        })));
+        assert_eq!(parse_args(&to_args("run all")), Ok(Command::Run(RunOptions {
+            day: None,
+            parts: vec![Part::A, Part::B],
+            input: Input::Default,
+            json: false,
+        })));
+        assert_eq!(parse_args(&to_args("run 3 --input in.txt a")), Ok(Command::Run(RunOptions {
+            day: Some(3),
+            parts: vec![Part::A],
+            input: Input::File("in.txt".to_string()),
+            json: false,
+        })));
+        assert_eq!(parse_args(&to_args("run 3 --input - --json")), Ok(Command::Run(RunOptions {
+            day: Some(3),
+            parts: vec![Part::A, Part::B],
+            input: Input::Stdin,
+            json: true,
+        })));
+
+        assert!(parse_args(&to_args("run")).is_err());
+        assert!(parse_args(&to_args("run 26")).is_err());
+        assert!(parse_args(&to_args("run 3 c")).is_err());
+        assert!(parse_args(&to_args("run 3 --input")).is_err());
+        assert!(parse_args(&to_args("run all --input in.txt")).is_err());
+        assert!(parse_args(&to_args("walk 3")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify_args() {
+        assert_eq!(parse_args(&to_args("verify")), Ok(Command::Verify(VerifyOptions {
+            day: None,
+            answers_file: "answers.toml".to_string(),
+        })));
+        assert_eq!(parse_args(&to_args("verify 8 --answers mine.toml")), Ok(Command::Verify(VerifyOptions {
+            day: Some(8),
+            answers_file: "mine.toml".to_string(),
+        })));
+
+        assert!(parse_args(&to_args("verify 0")).is_err());
+        assert!(parse_args(&to_args("verify --answers")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(parse_args(&to_args("bench")), Ok(Command::Bench(BenchOptions {
+            day: None,
+            warmup_runs: 1,
+            runs: 5,
+            history_file: "bench_history.csv".to_string(),
+            save: true,
+            threshold: 0.1,
+            json: false,
+        })));
+        assert_eq!(
+            parse_args(&to_args("bench 16 --runs 3 --warmup 0 --threshold 25 --no-save --json \
+                --history h.csv")),
+            Ok(Command::Bench(BenchOptions {
+                day: Some(16),
+                warmup_runs: 0,
+                runs: 3,
+                history_file: "h.csv".to_string(),
+                save: false,
+                threshold: 0.25,
+                json: true,
+            })));
+
+        assert!(parse_args(&to_args("bench --runs 0")).is_err());
+        assert!(parse_args(&to_args("bench --runs x")).is_err());
+        assert!(parse_args(&to_args("bench --warmup")).is_err());
+    }
+
+    #[test]
+    fn test_same_answer() {
+        assert!(same_answer(&Answer::Integer(42), "42"));
+        assert!(same_answer(&Answer::Text(" #\n# \n".to_string()), " #\n# "));
+        assert!(!same_answer(&Answer::Integer(42), "43"));
+    }
+
+    #[test]
+    fn test_solutions_are_in_order() {
+        let days = get_solutions().iter()
+            .map(|solution| solution.get_day())
+            .collect::<Vec<u32>>();
+        assert_eq!(days, (1..=22).collect::<Vec<u32>>());
+    }
+}
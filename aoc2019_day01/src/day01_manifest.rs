@@ -0,0 +1,346 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc2019_utils::json::Json;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Module {
+    pub name: String,
+    pub group: Option<String>,
+    pub mass: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Manifest {
+    pub modules: Vec<Module>,
+}
+
+/// Parses a manifest. Each line is a mass, optionally after a name, and
+/// `[group]` lines put the modules after them in a group. Blank lines and
+/// `#` comments are skipped, so a plain list of masses is a manifest too.
+pub fn parse_manifest(txt: &str) -> Result<Manifest, String> {
+    let mut manifest = Manifest::default();
+    let mut group = None;
+    // Modules without a name are named by their position, and those names
+    // can't clash with given ones either.
+    let mut names = HashSet::new();
+
+    for (i, line) in txt.lines().enumerate() {
+        let line_num = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']')
+                .ok_or_else(|| format!("line {}: group is missing its ']'", line_num))?
+                .trim();
+            if name.is_empty() {
+                return Err(format!("line {}: group needs a name", line_num));
+            }
+            group = Some(name.to_string());
+            continue;
+        }
+
+        let (name, mass_txt) = match line.rfind(char::is_whitespace) {
+            Some(idx) => (Some(line[..idx].trim()), &line[(idx + 1)..]),
+            None => (None, line),
+        };
+        let mass = i64::from_str(mass_txt)
+            .map_err(|_| format!("line {}: bad mass: {}", line_num, mass_txt))?;
+        if mass < 0 {
+            return Err(format!("line {}: mass can't be negative: {}", line_num, mass));
+        }
+
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => format!("module {}", manifest.modules.len() + 1),
+        };
+        if !names.insert((group.clone(), name.clone())) {
+            return Err(format!("line {}: duplicate module: {}", line_num, name));
+        }
+
+        manifest.modules.push(Module { name, group: group.clone(), mass });
+    }
+
+    Ok(manifest)
+}
+
+pub fn calc_fuel(mass: i64) -> i64 {
+    std::cmp::max(mass / 3 - 2, 0)
+}
+
+/// The fuel for one module. `chain` starts with the fuel for the module's
+/// mass, and each step after is the fuel needed to lift the step before.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ModuleFuel {
+    pub module: Module,
+    pub chain: Vec<i64>,
+}
+
+impl ModuleFuel {
+    pub fn new(module: &Module) -> Self {
+        let mut chain = vec![];
+        let mut fuel = calc_fuel(module.mass);
+        while fuel > 0 {
+            chain.push(fuel);
+            fuel = calc_fuel(fuel);
+        }
+        Self { module: module.clone(), chain }
+    }
+
+    pub fn fuel(&self) -> i64 {
+        self.chain.first().copied().unwrap_or(0)
+    }
+
+    pub fn total_fuel(&self) -> i64 {
+        self.chain.iter().sum()
+    }
+}
+
+/// Masses and fuel added up over some modules.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct FuelTotals {
+    pub mass: i64,
+    pub fuel: i64,
+    pub total_fuel: i64,
+}
+
+impl FuelTotals {
+    fn add(&mut self, module_fuel: &ModuleFuel) {
+        self.mass += module_fuel.module.mass;
+        self.fuel += module_fuel.fuel();
+        self.total_fuel += module_fuel.total_fuel();
+    }
+
+    fn to_json_fields(self) -> Vec<(&'static str, Json)> {
+        vec![
+            ("mass", self.mass.into()),
+            ("fuel", self.fuel.into()),
+            ("total_fuel", self.total_fuel.into()),
+        ]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FuelReport {
+    pub modules: Vec<ModuleFuel>,
+}
+
+fn format_table(rows: &[Vec<String>]) -> String {
+    let num_cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths = (0..num_cols)
+        .map(|col| rows.iter().filter_map(|row| row.get(col)).map(|cell| cell.len()).max())
+        .map(|width| width.unwrap_or(0))
+        .collect::<Vec<usize>>();
+
+    rows.iter()
+        .map(|row| {
+            let line = row.iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(col, (cell, width))| match col {
+                    2..=4 => format!("{:>1$}", cell, width),
+                    _ => format!("{:<1$}", cell, width),
+                })
+                .collect::<Vec<String>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+impl FuelReport {
+    pub fn new(manifest: &Manifest) -> Self {
+        Self { modules: manifest.modules.iter().map(ModuleFuel::new).collect() }
+    }
+
+    pub fn totals(&self) -> FuelTotals {
+        let mut totals = FuelTotals::default();
+        self.modules.iter().for_each(|module_fuel| totals.add(module_fuel));
+        totals
+    }
+
+    /// Totals for each group, in the order the groups first show up.
+    /// Modules that aren't in a group are under `None`.
+    pub fn group_totals(&self) -> Vec<(Option<String>, FuelTotals)> {
+        let mut groups: Vec<(Option<String>, FuelTotals)> = vec![];
+        for module_fuel in &self.modules {
+            let group = &module_fuel.module.group;
+            let idx = match groups.iter().position(|(name, _)| name == group) {
+                Some(idx) => idx,
+                None => {
+                    groups.push((group.clone(), FuelTotals::default()));
+                    groups.len() - 1
+                },
+            };
+            groups[idx].1.add(module_fuel);
+        }
+        groups
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![
+            ["module", "group", "mass", "fuel", "total fuel", "chain"].iter()
+                .map(|heading| heading.to_string())
+                .collect::<Vec<String>>(),
+        ];
+        rows.extend(self.modules.iter().map(|module_fuel| vec![
+            module_fuel.module.name.clone(),
+            module_fuel.module.group.clone().unwrap_or_else(|| "-".to_string()),
+            module_fuel.module.mass.to_string(),
+            module_fuel.fuel().to_string(),
+            module_fuel.total_fuel().to_string(),
+            module_fuel.chain.iter()
+                .map(|fuel| fuel.to_string())
+                .collect::<Vec<String>>()
+                .join(" + "),
+        ]));
+
+        let total_row = |name: &str, group: &str, totals: FuelTotals| vec![
+            name.to_string(),
+            group.to_string(),
+            totals.mass.to_string(),
+            totals.fuel.to_string(),
+            totals.total_fuel.to_string(),
+        ];
+        let groups = self.group_totals();
+        if groups.iter().any(|(group, _)| group.is_some()) {
+            rows.extend(groups.into_iter().map(|(group, totals)| {
+                total_row("(group)", group.as_deref().unwrap_or("-"), totals)
+            }));
+        }
+        rows.push(total_row("(all)", "", self.totals()));
+
+        format_table(&rows)
+    }
+
+    pub fn to_json(&self) -> Json {
+        let modules = self.modules.iter()
+            .map(|module_fuel| Json::object(vec![
+                ("name", module_fuel.module.name.as_str().into()),
+                ("group", module_fuel.module.group.clone().into()),
+                ("mass", module_fuel.module.mass.into()),
+                ("fuel", module_fuel.fuel().into()),
+                ("total_fuel", module_fuel.total_fuel().into()),
+                ("chain", module_fuel.chain.clone().into()),
+            ]))
+            .collect::<Vec<Json>>();
+        let groups = self.group_totals().into_iter()
+            .map(|(group, totals)| {
+                let mut fields = vec![("name", group.into())];
+                fields.extend(totals.to_json_fields());
+                Json::object(fields)
+            })
+            .collect::<Vec<Json>>();
+
+        let mut fields = vec![("modules", Json::Array(modules)), ("groups", Json::Array(groups))];
+        fields.extend(self.totals().to_json_fields());
+        Json::object(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = concat!(
+        "# the examples from the puzzle\n",
+        "12\n",
+        "\n",
+        "[booster]\n",
+        "left thruster 1969\n",
+        "right thruster 100756\n",
+        "[ lander ]\n",
+        "14\n",
+    );
+
+    #[test]
+    fn test_calc_fuel() {
+        assert_eq!(calc_fuel(0), 0);
+        assert_eq!(calc_fuel(3), 0);
+        assert_eq!(calc_fuel(12), 2);
+        assert_eq!(calc_fuel(14), 2);
+        assert_eq!(calc_fuel(1969), 654);
+        assert_eq!(calc_fuel(100756), 33583);
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = parse_manifest(MANIFEST).unwrap();
+        let module = |name: &str, group: Option<&str>, mass| Module {
+            name: name.to_string(),
+            group: group.map(String::from),
+            mass,
+        };
+        assert_eq!(manifest.modules, vec![
+            module("module 1", None, 12),
+            module("left thruster", Some("booster"), 1969),
+            module("right thruster", Some("booster"), 100756),
+            module("module 4", Some("lander"), 14),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_manifest_errors() {
+        assert_eq!(parse_manifest("12\nthruster x\n"), Err("line 2: bad mass: x".to_string()));
+        assert_eq!(parse_manifest("-5\n"), Err("line 1: mass can't be negative: -5".to_string()));
+        assert_eq!(parse_manifest("[a\n"), Err("line 1: group is missing its ']'".to_string()));
+        assert_eq!(parse_manifest("[ ]\n"), Err("line 1: group needs a name".to_string()));
+        assert_eq!(parse_manifest("a 1\nb 2\na 3\n"),
+            Err("line 3: duplicate module: a".to_string()));
+        assert_eq!(parse_manifest("a 1\n[g]\na 2\n").map(|manifest| manifest.modules.len()),
+            Ok(2));
+
+        // Names from a module's position clash with given ones too.
+        assert_eq!(parse_manifest("module 2 5\n7\n"),
+            Err("line 2: duplicate module: module 2".to_string()));
+        assert_eq!(parse_manifest("5\nmodule 1 7\n"),
+            Err("line 2: duplicate module: module 1".to_string()));
+        assert_eq!(parse_manifest("5\n[g]\nmodule 1 7\n").map(|manifest| manifest.modules.len()),
+            Ok(2));
+    }
+
+    #[test]
+    fn test_fuel_report() {
+        let report = FuelReport::new(&parse_manifest(MANIFEST).unwrap());
+        assert_eq!(report.modules[1].chain, vec![654, 216, 70, 21, 5]);
+        assert_eq!(report.totals(), FuelTotals {
+            mass: 12 + 1969 + 100756 + 14,
+            fuel: 2 + 654 + 33583 + 2,
+            total_fuel: 2 + 966 + 50346 + 2,
+        });
+        assert_eq!(report.group_totals()[1], (Some("booster".to_string()), FuelTotals {
+            mass: 1969 + 100756,
+            fuel: 654 + 33583,
+            total_fuel: 966 + 50346,
+        }));
+
+        // Masses this big would overflow an i32.
+        let big = FuelReport::new(&parse_manifest("30000000000\n").unwrap());
+        assert_eq!(big.totals().fuel, 9999999998);
+    }
+
+    #[test]
+    fn test_report_output() {
+        let report = FuelReport::new(&parse_manifest("a 12\n[g]\nb 1969\n").unwrap());
+        assert_eq!(report.to_table(), concat!(
+            "module   group  mass  fuel  total fuel  chain\n",
+            "a        -        12     2           2  2\n",
+            "b        g      1969   654         966  654 + 216 + 70 + 21 + 5\n",
+            "(group)  -        12     2           2\n",
+            "(group)  g      1969   654         966\n",
+            "(all)           1981   656         968\n",
+        ));
+        assert_eq!(report.to_json().to_string(), concat!(
+            r#"{"modules":["#,
+            r#"{"name":"a","group":null,"mass":12,"fuel":2,"total_fuel":2,"chain":[2]},"#,
+            r#"{"name":"b","group":"g","mass":1969,"fuel":654,"total_fuel":966,"#,
+            r#""chain":[654,216,70,21,5]}],"#,
+            r#""groups":[{"name":null,"mass":12,"fuel":2,"total_fuel":2},"#,
+            r#"{"name":"g","mass":1969,"fuel":654,"total_fuel":966}],"#,
+            r#""mass":1981,"fuel":656,"total_fuel":968}"#,
+        ));
+    }
+}
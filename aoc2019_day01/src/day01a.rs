@@ -1,9 +1,11 @@
-pub mod day01_utils;
+use aoc2019_day01::day01_utils;
 
 use aoc2019_utils;
 
 fn main() {
     let input = aoc2019_utils::get_input("inputs/day01.txt");
-    let fuel = day01_utils::calc_fuel_from_str(&input);
-    println!("total fuel: {}", fuel);
+    match day01_utils::calc_fuel_from_str(&input) {
+        Ok(fuel) => println!("total fuel: {}", fuel),
+        Err(err) => println!("bad manifest: {}", err),
+    }
 }
@@ -0,0 +1,36 @@
+use aoc2019_day01::day01_manifest;
+
+use day01_manifest::*;
+
+const USAGE: &str = "usage: aoc2019_day01report [manifest file] [--json]";
+
+fn main() {
+    let mut filename = "inputs/day01.txt".to_string();
+    let mut json = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("unknown arg: {}\n{}", arg, USAGE);
+                std::process::exit(1);
+            },
+            _ => filename = arg,
+        }
+    }
+
+    let input = aoc2019_utils::get_input(&filename);
+    let manifest = match parse_manifest(&input) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("bad manifest: {}: {}", filename, err);
+            std::process::exit(1);
+        },
+    };
+
+    let report = FuelReport::new(&manifest);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_table());
+    }
+}
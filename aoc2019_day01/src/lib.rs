@@ -0,0 +1,38 @@
+pub mod day01_manifest;
+pub mod day01_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day01_manifest::*;
+
+fn get_answer<F>(manifest: &Result<Manifest, String>, get_fuel: F) -> Answer
+where
+    F: Fn(&FuelTotals) -> i64,
+{
+    match manifest {
+        Ok(manifest) => get_fuel(&FuelReport::new(manifest).totals()).into(),
+        Err(err) => err.as_str().into(),
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Result<Manifest, String>;
+
+    fn get_day(&self) -> u32 {
+        1
+    }
+
+    fn parse(&self, input: &str) -> Result<Manifest, String> {
+        parse_manifest(input)
+    }
+
+    fn solve_a(&self, manifest: &Result<Manifest, String>) -> Answer {
+        get_answer(manifest, |totals| totals.fuel)
+    }
+
+    fn solve_b(&self, manifest: &Result<Manifest, String>) -> Answer {
+        get_answer(manifest, |totals| totals.total_fuel)
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::day02_utils::{ADD_OP, END_OP, MUL_OP};
+
+pub const NOUN_VERB_RANGE: RangeInclusive<i64> = 0..=99;
+
+/// An expression in the values put in `mem[1]` and `mem[2]`, with `noun` and
+/// `verb` as their coefficients.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Affine {
+    pub noun: i64,
+    pub verb: i64,
+    pub constant: i64,
+}
+
+impl Affine {
+    pub const NOUN: Affine = Affine { noun: 1, verb: 0, constant: 0 };
+    pub const VERB: Affine = Affine { noun: 0, verb: 1, constant: 0 };
+
+    pub fn constant(val: i64) -> Self {
+        Self { noun: 0, verb: 0, constant: val }
+    }
+
+    pub fn as_constant(&self) -> Option<i64> {
+        if self.noun == 0 && self.verb == 0 {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    pub fn eval(&self, noun: i64, verb: i64) -> i64 {
+        self.noun * noun + self.verb * verb + self.constant
+    }
+
+    /// The smallest and largest values over every noun and verb in `range`.
+    /// An affine expression hits both at corners of the range.
+    pub fn bounds(&self, range: &RangeInclusive<i64>) -> (i64, i64) {
+        let (lo, hi) = (*range.start(), *range.end());
+        let corners = [self.eval(lo, lo), self.eval(lo, hi), self.eval(hi, lo), self.eval(hi, hi)];
+        (*corners.iter().min().unwrap(), *corners.iter().max().unwrap())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            noun: self.noun + other.noun,
+            verb: self.verb + other.verb,
+            constant: self.constant + other.constant,
+        }
+    }
+
+    fn scale(&self, factor: i64) -> Self {
+        Self {
+            noun: self.noun * factor,
+            verb: self.verb * factor,
+            constant: self.constant * factor,
+        }
+    }
+}
+
+impl fmt::Display for Affine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} * noun + {} * verb + {}", self.noun, self.verb, self.constant)
+    }
+}
+
+/// Why a value stopped being affine.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Opaque {
+    /// Two values that both depend on the noun or verb were multiplied.
+    Product,
+    /// The value was read from an address that depends on the noun or verb.
+    IndirectRead,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Value {
+    Known(Affine),
+    /// Made by the instruction at `addr`. It's only a problem if it ends up
+    /// in `mem[0]` or gets used as an opcode or address.
+    Opaque { addr: usize, why: Opaque },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SymbolicError {
+    OutOfBounds { addr: usize },
+    BadOpcode { addr: usize, op: i64 },
+    /// The opcode at `addr` depends on the noun or verb.
+    SymbolicOpcode { addr: usize },
+    /// The instruction at `addr` writes to an address that depends on the noun
+    /// or verb, so there's no telling what it overwrites.
+    SymbolicWrite { addr: usize },
+    /// `mem[0]` isn't affine, because of the instruction at `addr`.
+    NotAffine { addr: usize, why: Opaque },
+}
+
+impl fmt::Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { addr } => write!(f, "address {} is out of bounds", addr),
+            Self::BadOpcode { addr, op } => write!(f, "bad opcode at {}: {}", addr, op),
+            Self::SymbolicOpcode { addr } => {
+                write!(f, "the opcode at {} depends on the noun and verb", addr)
+            },
+            Self::SymbolicWrite { addr } => {
+                write!(f, "the instruction at {} writes to an address that depends on \
+                    the noun and verb", addr)
+            },
+            Self::NotAffine { addr, why: Opaque::Product } => {
+                write!(f, "the output isn't affine: the instruction at {} multiplies \
+                    the noun and verb", addr)
+            },
+            Self::NotAffine { addr, why: Opaque::IndirectRead } => {
+                write!(f, "the output isn't affine: the instruction at {} reads from \
+                    an address that depends on the noun and verb", addr)
+            },
+        }
+    }
+}
+
+/// Runs a program with `mem[1]` and `mem[2]` left as the noun and verb
+/// variables, tracking every value as an affine expression of them.
+pub struct SymbolicCpu {
+    mem: Vec<Value>,
+    instr_ptr: usize,
+}
+
+impl SymbolicCpu {
+    pub fn new(prog: &[i64]) -> SymbolicCpu {
+        let mut mem = prog.iter()
+            .map(|val| Value::Known(Affine::constant(*val)))
+            .collect::<Vec<Value>>();
+        if mem.len() > 2 {
+            mem[1] = Value::Known(Affine::NOUN);
+            mem[2] = Value::Known(Affine::VERB);
+        }
+        SymbolicCpu { mem, instr_ptr: 0 }
+    }
+
+    fn read(&self, addr: usize) -> Result<Value, SymbolicError> {
+        self.mem.get(addr).copied().ok_or(SymbolicError::OutOfBounds { addr })
+    }
+
+    /// Reads a value that has to be the same for every noun and verb.
+    fn read_concrete(&self, addr: usize) -> Result<Option<i64>, SymbolicError> {
+        Ok(match self.read(addr)? {
+            Value::Known(expr) => expr.as_constant(),
+            Value::Opaque { .. } => None,
+        })
+    }
+
+    fn read_param(&self, addr: usize) -> Result<Value, SymbolicError> {
+        let ptr = self.instr_ptr;
+        match self.read(addr)? {
+            Value::Known(expr) => match expr.as_constant() {
+                Some(src) if src >= 0 => self.read(src as usize),
+                Some(_) => Err(SymbolicError::OutOfBounds { addr }),
+                // The real CPU would read from wherever this lands, so every
+                // noun and verb has to keep it inside memory.
+                None => {
+                    let (min, max) = expr.bounds(&NOUN_VERB_RANGE);
+                    if min < 0 || max >= self.mem.len() as i64 {
+                        Err(SymbolicError::OutOfBounds { addr })
+                    } else {
+                        Ok(Value::Opaque { addr: ptr, why: Opaque::IndirectRead })
+                    }
+                },
+            },
+            Value::Opaque { .. } => Ok(Value::Opaque { addr: ptr, why: Opaque::IndirectRead }),
+        }
+    }
+
+    pub fn exec(&mut self) -> Result<bool, SymbolicError> {
+        let ptr = self.instr_ptr;
+        if ptr >= self.mem.len() {
+            return Ok(false);
+        }
+
+        let op = self.read_concrete(ptr)?
+            .ok_or(SymbolicError::SymbolicOpcode { addr: ptr })?;
+        if op == END_OP {
+            return Ok(false);
+        }
+        if op != ADD_OP && op != MUL_OP {
+            return Err(SymbolicError::BadOpcode { addr: ptr, op });
+        }
+
+        let src1 = self.read_param(ptr + 1)?;
+        let src2 = self.read_param(ptr + 2)?;
+        let dest = match self.read_concrete(ptr + 3)? {
+            Some(dest) if dest >= 0 && (dest as usize) < self.mem.len() => dest as usize,
+            Some(_) => return Err(SymbolicError::OutOfBounds { addr: ptr + 3 }),
+            None => return Err(SymbolicError::SymbolicWrite { addr: ptr }),
+        };
+
+        self.mem[dest] = match (src1, src2) {
+            (Value::Opaque { .. }, _) => src1,
+            (_, Value::Opaque { .. }) => src2,
+            (Value::Known(lhs), Value::Known(rhs)) if op == ADD_OP => Value::Known(lhs.add(&rhs)),
+            (Value::Known(lhs), Value::Known(rhs)) => {
+                match (lhs.as_constant(), rhs.as_constant()) {
+                    (Some(factor), _) => Value::Known(rhs.scale(factor)),
+                    (_, Some(factor)) => Value::Known(lhs.scale(factor)),
+                    (None, None) => Value::Opaque { addr: ptr, why: Opaque::Product },
+                }
+            },
+        };
+
+        self.instr_ptr += 4;
+        Ok(self.instr_ptr < self.mem.len())
+    }
+
+    pub fn exec_prog(&mut self) -> Result<(), SymbolicError> {
+        self.instr_ptr = 0;
+        while self.exec()? {}
+        Ok(())
+    }
+
+    pub fn get_output(&self) -> Result<Affine, SymbolicError> {
+        match self.read(0)? {
+            Value::Known(expr) => Ok(expr),
+            Value::Opaque { addr, why } => Err(SymbolicError::NotAffine { addr, why }),
+        }
+    }
+}
+
+/// What the program leaves in `mem[0]`, in terms of the noun and verb.
+pub fn get_output_expr(prog: &[i64]) -> Result<Affine, SymbolicError> {
+    let mut cpu = SymbolicCpu::new(prog);
+    cpu.exec_prog()?;
+    cpu.get_output()
+}
+
+/// Every noun and verb in `range` for which `expr` comes to `target`.
+pub fn solve(expr: &Affine, target: i64, range: RangeInclusive<i64>) -> Vec<(i64, i64)> {
+    range.clone()
+        .flat_map(|noun| {
+            let rest = target - expr.constant - expr.noun * noun;
+            let verbs = if expr.verb == 0 {
+                if rest == 0 { range.clone().collect() } else { vec![] }
+            } else if rest % expr.verb == 0 && range.contains(&(rest / expr.verb)) {
+                vec![rest / expr.verb]
+            } else {
+                vec![]
+            };
+            verbs.into_iter().map(move |verb| (noun, verb))
+        })
+        .collect()
+}
+
+pub fn solve_noun_verb(prog: &[i64], target: i64) -> Result<Vec<(i64, i64)>, SymbolicError> {
+    get_output_expr(prog).map(|expr| solve(&expr, target, NOUN_VERB_RANGE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02_utils::*;
+
+    // Pads a program so any noun or verb is an address inside it, like the
+    // puzzle inputs are, and so is their sum.
+    fn padded(prog: &[i64]) -> Vec<i64> {
+        let mut prog = prog.to_vec();
+        prog.resize(200, 0);
+        prog
+    }
+
+    // Reads mem[noun] + mem[verb] into a dead cell first, like the puzzle
+    // inputs do, then leaves 99 * noun + verb in mem[0].
+    fn make_prog() -> Vec<i64> {
+        padded(&[1, 0, 0, 3, 1, 1, 2, 3, 2, 1, 16, 0, 1, 0, 2, 0, 99])
+    }
+
+    #[test]
+    fn test_get_output_expr() {
+        let prog = make_prog();
+        let expr = get_output_expr(&prog).unwrap();
+        assert_eq!(expr, Affine { noun: 99, verb: 1, constant: 0 });
+        assert_eq!(expr.eval(12, 2), run_with_noun_verb(&prog, 12, 2));
+        assert_eq!(expr.to_string(), "99 * noun + 1 * verb + 0");
+    }
+
+    #[test]
+    fn test_solve() {
+        let prog = make_prog();
+        let target = 99 * 12 + 34;
+        assert_eq!(solve_noun_verb(&prog, target), Ok(vec![(12, 34)]));
+        assert_eq!(find_noun_verb(&prog, target), Some((12, 34)));
+
+        // With no verb term every verb works.
+        let expr = Affine { noun: 2, verb: 0, constant: 1 };
+        assert_eq!(solve(&expr, 5, 0..=3), vec![(2, 0), (2, 1), (2, 2), (2, 3)]);
+        assert_eq!(solve(&Affine::VERB.scale(3), 7, 0..=99), vec![]);
+    }
+
+    #[test]
+    fn test_not_affine() {
+        // Each starts with a dead read through the noun and verb, which is fine.
+        assert_eq!(get_output_expr(&padded(&[1, 0, 0, 3, 2, 1, 2, 0, 99])),
+            Err(SymbolicError::NotAffine { addr: 4, why: Opaque::Product }));
+        assert_eq!(get_output_expr(&padded(&[1, 0, 0, 3, 1, 1, 2, 10, 1, 0, 0, 0, 99])),
+            Err(SymbolicError::NotAffine { addr: 8, why: Opaque::IndirectRead }));
+        assert_eq!(get_output_expr(&padded(&[1, 0, 0, 3, 1, 1, 2, 11, 1, 0, 0, 0, 99])),
+            Err(SymbolicError::SymbolicWrite { addr: 8 }));
+        assert_eq!(get_output_expr(&padded(&[1, 0, 0, 3, 1, 1, 2, 8, 0, 0, 0, 0, 99])),
+            Err(SymbolicError::SymbolicOpcode { addr: 8 }));
+    }
+
+    #[test]
+    fn test_symbolic_read_out_of_bounds() {
+        // Too short for mem[noun] to exist once the noun gets past 16.
+        let prog = [1, 0, 0, 3, 1, 1, 2, 3, 2, 1, 16, 0, 1, 0, 2, 0, 99];
+        assert_eq!(solve_noun_verb(&prog, 99 * 12 + 34),
+            Err(SymbolicError::OutOfBounds { addr: 1 }));
+
+        // Fine with a big enough program, but noun - verb can go negative.
+        let prog = padded(&[1, 0, 0, 3, 2, 2, 17, 18, 1, 1, 18, 13, 1, 0, 0, 0, 99, -1, 0]);
+        assert_eq!(get_output_expr(&prog), Err(SymbolicError::OutOfBounds { addr: 13 }));
+
+        let expr = Affine { noun: 1, verb: -1, constant: 0 };
+        assert_eq!(expr.bounds(&NOUN_VERB_RANGE), (-99, 99));
+    }
+}
@@ -74,9 +74,9 @@ mod tests {
      }
 }
 
-const ADD_OP: i64 = 1;
-const MUL_OP: i64 = 2;
-const END_OP: i64 = 99;
+pub const ADD_OP: i64 = 1;
+pub const MUL_OP: i64 = 2;
+pub const END_OP: i64 = 99;
 
 pub struct Cpu {
     pub mem: Vec<i64>,
@@ -132,3 +132,17 @@ pub fn parse_prog(instr_txt: &str) -> Vec<i64> {
         .map(|num_str| i64::from_str(num_str).unwrap())
         .collect::<Vec<i64>>()
 }
+
+pub fn run_with_noun_verb(prog: &[i64], noun: i64, verb: i64) -> i64 {
+    let mut cpu = Cpu::new(prog.to_vec());
+    cpu.mem[1] = noun;
+    cpu.mem[2] = verb;
+    cpu.exec_prog();
+    cpu.mem[0]
+}
+
+pub fn find_noun_verb(prog: &[i64], target: i64) -> Option<(i64, i64)> {
+    (0..=99)
+        .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
+        .find(|(noun, verb)| run_with_noun_verb(prog, *noun, *verb) == target)
+}
@@ -1,11 +1,8 @@
-pub mod day02_utils;
+use aoc2019_day02::day02_utils;
 
 fn main() {
     let input = aoc2019_utils::get_input("inputs/day02.txt");
     let prog = day02_utils::parse_prog(&input);
-    let mut cpu = day02_utils::Cpu::new(prog);
-    cpu.mem[1] = 12;
-    cpu.mem[2] = 2;
-    cpu.exec_prog();
-    println!("mem[0]: {}", cpu.mem[0]);
+    let result = day02_utils::run_with_noun_verb(&prog, 12, 2);
+    println!("mem[0]: {}", result);
 }
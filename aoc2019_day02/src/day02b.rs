@@ -1,28 +1,27 @@
-pub mod day02_utils;
+use aoc2019_day02::day02_symbolic;
+use aoc2019_day02::day02_utils;
 
-fn do_search(prog: &Vec<i64>) {
-    const TARGET_NUM: i64 = 19690720;
-
-    for noun in 0..=99 {
-        for verb in 0..=99 {
-            let mut cpu = day02_utils::Cpu::new(prog.clone());
-            cpu.mem[1] = noun;
-            cpu.mem[2] = verb;
-            cpu.exec_prog();
-
-            if cpu.mem[0] == TARGET_NUM {
-                println!("noun/verb: {}/{}", noun, verb);
-                println!("final answer: {}", noun * 100 + verb);
-                return;
-            }
-        }
-    }
-
-    println!("no solution found");
-}
+const TARGET_NUM: i64 = 19690720;
 
 fn main() {
     let input = aoc2019_utils::get_input("inputs/day02.txt");
     let prog = day02_utils::parse_prog(&input);
-    do_search(&prog);
+
+    let found = match day02_symbolic::get_output_expr(&prog) {
+        Ok(expr) => {
+            println!("mem[0] = {}", expr);
+            let pairs = day02_symbolic::solve(&expr, TARGET_NUM, day02_symbolic::NOUN_VERB_RANGE);
+            pairs.iter().for_each(|(noun, verb)| println!("noun/verb: {}/{}", noun, verb));
+            pairs.first().copied()
+        },
+        Err(err) => {
+            println!("can't solve symbolically ({}), searching instead", err);
+            day02_utils::find_noun_verb(&prog, TARGET_NUM)
+        },
+    };
+
+    match found {
+        Some((noun, verb)) => println!("final answer: {}", noun * 100 + verb),
+        None => println!("no solution found"),
+    }
 }
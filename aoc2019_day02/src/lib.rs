@@ -0,0 +1,35 @@
+pub mod day02_symbolic;
+pub mod day02_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day02_symbolic::*;
+use day02_utils::*;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        2
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        run_with_noun_verb(prog, 12, 2).into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        // Only fall back on trying every noun and verb if the program can't be
+        // solved symbolically.
+        let found = match solve_noun_verb(prog, 19690720) {
+            Ok(pairs) => pairs.first().copied(),
+            Err(_) => find_noun_verb(prog, 19690720),
+        };
+        found.map(|(noun, verb)| noun * 100 + verb).into()
+    }
+}
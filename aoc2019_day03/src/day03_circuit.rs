@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::day03_utils::*;
+
+/// A straight run of wire. `steps` is how far along the wire `start` is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Segment {
+    pub start: GridPoint,
+    pub end: GridPoint,
+    pub steps: u32,
+    pub is_vertical: bool,
+}
+
+impl Segment {
+    fn x_range(&self) -> (i32, i32) {
+        (self.start.x.min(self.end.x), self.start.x.max(self.end.x))
+    }
+
+    fn y_range(&self) -> (i32, i32) {
+        (self.start.y.min(self.end.y), self.start.y.max(self.end.y))
+    }
+
+    pub fn contains(&self, pos: GridPoint) -> bool {
+        let (min_x, max_x) = self.x_range();
+        let (min_y, max_y) = self.y_range();
+        (min_x..=max_x).contains(&pos.x) && (min_y..=max_y).contains(&pos.y)
+    }
+
+    /// How far along the wire `pos` is, if it's on this segment.
+    pub fn steps_to(&self, pos: GridPoint) -> Option<u32> {
+        if self.contains(pos) {
+            Some(self.steps + self.start.manhattan(pos) as u32)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where two wires cross. `wires` are indexes into the circuit's wires, lowest
+/// first, and `steps` are how far along each wire the crossing first is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Intersection {
+    pub pos: GridPoint,
+    pub wires: [usize; 2],
+    pub steps: [u32; 2],
+}
+
+impl Intersection {
+    pub fn delay(&self) -> u32 {
+        self.steps[0] + self.steps[1]
+    }
+}
+
+/// Wires laid out as segments rather than every point on them, so the work
+/// depends on how many turns they take, not how long they are.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Circuit {
+    pub start_pos: GridPoint,
+    pub wires: Vec<Vec<Segment>>,
+}
+
+// Sweep events at the same x go in this order, so that a vertical segment
+// meets horizontal ones that start or end at its x.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+enum EventKind {
+    AddHorizontal,
+    Vertical,
+    RemoveHorizontal,
+}
+
+impl Circuit {
+    pub fn new<W: AsRef<[Move]>>(start_pos: GridPoint, wires: &[W]) -> Self {
+        let wires = wires.iter()
+            .map(|moves| {
+                let mut pos = start_pos;
+                let mut steps = 0;
+                moves.as_ref().iter()
+                    .map(|mov| {
+                        let end = pos + mov.dir.offset() * mov.dist as i32;
+                        let segment = Segment {
+                            start: pos,
+                            end,
+                            steps,
+                            is_vertical: mov.dir.is_vertical(),
+                        };
+                        pos = end;
+                        steps += mov.dist;
+                        segment
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { start_pos, wires }
+    }
+
+    /// The segment `wire` is on when it first gets to `pos`.
+    fn first_segment(&self, wire: usize, pos: GridPoint) -> Option<&Segment> {
+        self.wires[wire].iter().find(|segment| segment.contains(pos))
+    }
+
+    /// How far along `wire` it first gets to `pos`.
+    pub fn steps_to(&self, wire: usize, pos: GridPoint) -> Option<u32> {
+        self.first_segment(wire, pos).and_then(|segment| segment.steps_to(pos))
+    }
+
+    /// Every place where a horizontal segment of one wire crosses a vertical
+    /// segment of another, apart from where they all start. Found with a sweep
+    /// line moving along x. A place doesn't count if both wires first get
+    /// there going the same way, as when one turns onto a run of the other.
+    pub fn intersections(&self) -> Vec<Intersection> {
+        let mut events = vec![];
+        for (wire, segments) in self.wires.iter().enumerate() {
+            for segment in segments {
+                let (min_x, max_x) = segment.x_range();
+                if segment.is_vertical {
+                    events.push((min_x, EventKind::Vertical, wire, segment));
+                } else {
+                    events.push((min_x, EventKind::AddHorizontal, wire, segment));
+                    events.push((max_x, EventKind::RemoveHorizontal, wire, segment));
+                }
+            }
+        }
+        events.sort_by_key(|(x, kind, _, _)| (*x, *kind));
+
+        let mut active = BTreeMap::<i32, Vec<(usize, &Segment)>>::new();
+        let mut found = HashSet::new();
+        let mut result = vec![];
+        for (x, kind, wire, segment) in events {
+            let y = segment.start.y;
+            match kind {
+                EventKind::AddHorizontal => active.entry(y).or_default().push((wire, segment)),
+                EventKind::RemoveHorizontal => {
+                    let at_y = active.get_mut(&y).unwrap();
+                    let idx = at_y.iter()
+                        .position(|(other_wire, other)| *other_wire == wire && *other == segment)
+                        .unwrap();
+                    at_y.swap_remove(idx);
+                },
+                EventKind::Vertical => {
+                    let (min_y, max_y) = segment.y_range();
+                    for (y, horizontals) in active.range(min_y..=max_y) {
+                        let pos = GridPoint { x, y: *y };
+                        for (other_wire, _) in horizontals {
+                            let wires = [wire.min(*other_wire), wire.max(*other_wire)];
+                            if wires[0] == wires[1] || pos == self.start_pos
+                                || !found.insert((pos, wires))
+                            {
+                                continue;
+                            }
+                            let firsts = [
+                                self.first_segment(wires[0], pos).unwrap(),
+                                self.first_segment(wires[1], pos).unwrap(),
+                            ];
+                            if firsts[0].is_vertical != firsts[1].is_vertical {
+                                result.push(Intersection {
+                                    pos,
+                                    wires,
+                                    steps: [
+                                        firsts[0].steps_to(pos).unwrap(),
+                                        firsts[1].steps_to(pos).unwrap(),
+                                    ],
+                                });
+                            }
+                        }
+                    }
+                },
+            }
+        }
+        result
+    }
+
+    /// The crossing closest to the start by Manhattan distance.
+    pub fn closest_intersection(&self) -> Option<Intersection> {
+        self.intersections().into_iter()
+            .min_by_key(|intersection| intersection.pos.manhattan(self.start_pos))
+    }
+
+    /// The crossing the signal gets to first along both wires.
+    pub fn earliest_intersection(&self) -> Option<Intersection> {
+        self.intersections().into_iter().min_by_key(|intersection| intersection.delay())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START_POS: GridPoint = GridPoint { x: 0, y: 0 };
+
+    #[test]
+    fn test_intersections() {
+        let circuit = Circuit::new(START_POS, &parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4"));
+        let mut intersections = circuit.intersections();
+        intersections.sort_by_key(|intersection| intersection.delay());
+        assert_eq!(intersections, vec![
+            Intersection { pos: GridPoint { x: 6, y: -5 }, wires: [0, 1], steps: [15, 15] },
+            Intersection { pos: GridPoint { x: 3, y: -3 }, wires: [0, 1], steps: [20, 20] },
+        ]);
+        assert_eq!(circuit.closest_intersection().map(|found| found.pos),
+            Some(GridPoint { x: 3, y: -3 }));
+    }
+
+    #[test]
+    fn test_intersections_going_the_same_way() {
+        // Both start off going right, so where the first turns up at (2, 0)
+        // isn't a crossing. They do cross at (2, -3) and (3, -3).
+        let circuit = Circuit::new(START_POS, &parse_wires("R2,U3,R10
+R3,U3,L5"));
+        assert_eq!(circuit.closest_intersection().map(|found| found.pos.manhattan(START_POS)),
+            Some(5));
+        assert_eq!(circuit.earliest_intersection().map(|found| found.delay()), Some(12));
+
+        // They share their first run, then turn away from each other.
+        let circuit = Circuit::new(START_POS, &parse_wires("U5,L5,D10
+U5,R5,D10"));
+        assert_eq!(circuit.intersections(), vec![]);
+    }
+
+    #[test]
+    fn test_many_wires() {
+        // The third wire crosses the first once and the second twice.
+        let wires = parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4\nD1,R2,U10");
+        let circuit = Circuit::new(START_POS, &wires);
+        let mut intersections = circuit.intersections();
+        intersections.sort_by_key(|intersection| (intersection.wires, intersection.delay()));
+        assert_eq!(intersections, vec![
+            Intersection { pos: GridPoint { x: 6, y: -5 }, wires: [0, 1], steps: [15, 15] },
+            Intersection { pos: GridPoint { x: 3, y: -3 }, wires: [0, 1], steps: [20, 20] },
+            Intersection { pos: GridPoint { x: 2, y: 0 }, wires: [0, 2], steps: [2, 4] },
+            Intersection { pos: GridPoint { x: 2, y: -7 }, wires: [1, 2], steps: [9, 11] },
+            Intersection { pos: GridPoint { x: 2, y: -3 }, wires: [1, 2], steps: [21, 7] },
+        ]);
+        assert_eq!(circuit.earliest_intersection().map(|found| found.wires), Some([0, 2]));
+    }
+}
@@ -0,0 +1,157 @@
+use aoc2019_utils::{parse_args, write_file};
+
+use crate::day03_circuit::*;
+use crate::day03_utils::*;
+
+const WIRE_COLORS: [&str; 6] = ["#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf"];
+const CLOSEST_COLOR: &str = "#e377c2";
+const EARLIEST_COLOR: &str = "#bcbd22";
+
+/// What to export from either Day 3 binary: `--svg <file>` draws the wires,
+/// and `--steps` labels each crossing with its steps.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RenderOptions {
+    pub svg_file: Option<String>,
+    pub show_steps: bool,
+}
+
+impl RenderOptions {
+    pub fn from_args() -> Self {
+        let mut options = Self::default();
+        parse_args("--svg <file>, --steps", |arg, next_arg| {
+            match arg {
+                "--svg" => options.svg_file = Some(next_arg()),
+                "--steps" => options.show_steps = true,
+                _ => return false,
+            }
+            true
+        });
+        options
+    }
+}
+
+pub fn do_renders(options: &RenderOptions, circuit: &Circuit) {
+    if let Some(filename) = &options.svg_file {
+        write_file(filename, &to_svg(circuit, options.show_steps));
+    }
+}
+
+fn get_bounds(circuit: &Circuit) -> (GridPoint, GridPoint) {
+    let points = circuit.wires.iter()
+        .flatten()
+        .flat_map(|segment| vec![segment.start, segment.end])
+        .chain(std::iter::once(circuit.start_pos));
+
+    let mut min = circuit.start_pos;
+    let mut max = circuit.start_pos;
+    for point in points {
+        min = GridPoint { x: min.x.min(point.x), y: min.y.min(point.y) };
+        max = GridPoint { x: max.x.max(point.x), y: max.y.max(point.y) };
+    }
+    (min, max)
+}
+
+/// Draws each wire in its own color, with the central port as a black square
+/// and a circle on every crossing. The closest crossing is filled pink and
+/// the earliest olive. With `show_steps` each crossing is labelled with the
+/// steps along its two wires.
+pub fn to_svg(circuit: &Circuit, show_steps: bool) -> String {
+    let (min, max) = get_bounds(circuit);
+    // Everything is sized off the layout, since wires can span a few steps or
+    // tens of thousands.
+    let extent = (max.x - min.x).max(max.y - min.y).max(1);
+    let stroke_width = (extent / 400).max(1);
+    let radius = stroke_width * 3;
+    let font_size = stroke_width * 8;
+    let margin = radius + font_size;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min.x - margin, min.y - margin,
+        max.x - min.x + 2 * margin, max.y - min.y + 2 * margin);
+    svg.push_str(&format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+        min.x - margin, min.y - margin,
+        max.x - min.x + 2 * margin, max.y - min.y + 2 * margin));
+
+    for (wire, segments) in circuit.wires.iter().enumerate() {
+        let points = std::iter::once(circuit.start_pos)
+            .chain(segments.iter().map(|segment| segment.end))
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect::<Vec<String>>();
+        svg.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" \
+                stroke-opacity=\"0.8\"/>\n",
+            points.join(" "), WIRE_COLORS[wire % WIRE_COLORS.len()], stroke_width));
+    }
+
+    svg.push_str(&format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"black\"/>\n",
+        circuit.start_pos.x - radius, circuit.start_pos.y - radius, 2 * radius, 2 * radius));
+
+    let mut intersections = circuit.intersections();
+    let closest = intersections.iter()
+        .min_by_key(|found| found.pos.manhattan(circuit.start_pos))
+        .map(|found| found.pos);
+    let earliest = intersections.iter()
+        .min_by_key(|found| found.delay())
+        .map(|found| found.pos);
+    intersections.sort_by_key(|found| (found.pos.y, found.pos.x, found.wires));
+    for found in &intersections {
+        let fill = if Some(found.pos) == closest {
+            CLOSEST_COLOR
+        } else if Some(found.pos) == earliest {
+            EARLIEST_COLOR
+        } else {
+            "none"
+        };
+        svg.push_str(&format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\" \
+                stroke-width=\"{}\"/>\n",
+            found.pos.x, found.pos.y, radius, fill, stroke_width));
+
+        if show_steps {
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"monospace\">\
+                    {}+{}={}</text>\n",
+                found.pos.x + radius, found.pos.y - radius, font_size,
+                found.steps[0], found.steps[1], found.delay()));
+        }
+    }
+
+    // An earliest crossing that's also the closest gets a ring in the
+    // earliest color, so both still show.
+    if let Some(pos) = closest.filter(|pos| Some(*pos) == earliest) {
+        svg.push_str(&format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\" \
+                stroke-width=\"{}\"/>\n",
+            pos.x, pos.y, radius * 2, EARLIEST_COLOR, stroke_width));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_svg() {
+        let start_pos = GridPoint { x: 0, y: 0 };
+        let circuit = Circuit::new(start_pos, &parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4"));
+        let svg = to_svg(&circuit, true);
+
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-11 -18 30 29\">\n"));
+        assert!(svg.contains("<polyline points=\"0,0 8,0 8,-5 3,-5 3,-2\" fill=\"none\" \
+            stroke=\"#1f77b4\""));
+        assert!(svg.contains("<polyline points=\"0,0 0,-7 6,-7 6,-3 2,-3\" fill=\"none\" \
+            stroke=\"#d62728\""));
+        assert!(svg.contains("<rect x=\"-3\" y=\"-3\" width=\"6\" height=\"6\" fill=\"black\"/>"));
+        assert!(svg.contains("<circle cx=\"3\" cy=\"-3\" r=\"3\" fill=\"#e377c2\""));
+        assert!(svg.contains("<circle cx=\"6\" cy=\"-5\" r=\"3\" fill=\"#bcbd22\""));
+        assert!(svg.contains(">15+15=30</text>"));
+        assert!(!to_svg(&circuit, false).contains("<text"));
+    }
+}
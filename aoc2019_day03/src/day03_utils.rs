@@ -1,45 +1,19 @@
-use std::collections::HashMap;
 use std::str::FromStr;
 
 use aoc2019_utils::*;
 
-pub type GridPoint = point_2d::Point2d<i32>;
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Dir { Up, Down, Left, Right }
+use point_2d::Direction as Dir;
 
-impl Dir {
-    fn is_parallel_to(self, other: Dir) -> bool {
-        let dir1_vert = match self {
-            Dir::Up | Dir::Down => true,
-            Dir::Left | Dir::Right => false,
-        };
-        let dir2_vert = match other {
-            Dir::Up | Dir::Down => true,
-            Dir::Left | Dir::Right => false,
-        };
+use crate::day03_circuit::Circuit;
 
-        dir1_vert == dir2_vert
-    }
-}
+pub type GridPoint = point_2d::Point2d<i32>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Move {
-    dir: Dir,
-    dist: u32,
+    pub dir: Dir,
+    pub dist: u32,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Wire {
-    dir: Dir,
-    dist: u32,
-}
-
-const NUM_WIRES: usize = 2;
-pub type GridSpace = [Option<Wire>; NUM_WIRES];
-
-pub type WireGrid = HashMap<GridPoint, GridSpace>;
-
 pub fn parse_moves(line: &str) -> Vec<Move> {
     line.split(',').map(|dir_str| {
         let first_byte = dir_str.as_bytes()[0];
@@ -54,105 +28,30 @@ pub fn parse_moves(line: &str) -> Vec<Move> {
     }).collect()
 }
 
-fn apply_moves_to_grid(
-    start_pos: GridPoint,
-    wire_num: usize,
-    moves: &Vec<Move>,
-    grid: &mut WireGrid,
-) {
-    let mut pos = start_pos;
-    let mut wire_dist = 1u32;
-
-    let mut moves = {
-        let mut new_moves = moves.clone();
-        new_moves.reverse();
-        new_moves
-    };
-
-    while !moves.is_empty() {
-        let mov = moves.last_mut().unwrap();
-        let new_pos = match mov.dir {
-            Dir::Up => GridPoint { x: pos.x, y: pos.y - 1 },
-            Dir::Down => GridPoint { x: pos.x, y: pos.y + 1 },
-            Dir::Left => GridPoint { x: pos.x - 1, y: pos.y },
-            Dir::Right => GridPoint { x: pos.x + 1, y: pos.y },
-        };
-
-        let new_move = Move { dir: mov.dir, dist: mov.dist - 1 };
-
-        let space = grid.entry(new_pos).or_insert([None; NUM_WIRES]);
-        if !space[wire_num].is_some() {
-            space[wire_num] = Some(Wire { dir: mov.dir, dist: wire_dist });
-        }
-
-        pos = new_pos;
-        *mov = new_move;
-
-        if new_move.dist == 0 {
-            moves.pop();
-        }
-
-        wire_dist += 1;
-    }
-}
-
-pub fn build_grid(
-    start_pos: GridPoint,
-    moves1: &Vec<Move>,
-    moves2: &Vec<Move>,
-) -> WireGrid {
-    let mut grid = WireGrid::new();
-    apply_moves_to_grid(start_pos, 0, &moves1, &mut grid);
-    apply_moves_to_grid(start_pos, 1, &moves2, &mut grid);
-    grid
-}
-
 pub fn get_closest_intersection(
     start_pos: GridPoint,
-    wire1: &Vec<Move>,
-    wire2: &Vec<Move>,
+    wire1: &[Move],
+    wire2: &[Move],
 ) -> Option<u32> {
-    let grid = build_grid(start_pos, &wire1, &wire2);
-
-    grid.iter()
-        .filter(|(_, space)| {
-            if let (Some(wire1), Some(wire2)) = (space[0], space[1]) {
-                !wire1.dir.is_parallel_to(wire2.dir)
-            } else {
-                false
-            }
-        })
-        .map(|(pos, _)| {
-            let dx = (pos.x - start_pos.x).abs();
-            let dy = (pos.y - start_pos.y).abs();
-            (dx + dy) as u32
-        })
-        .min()
+    let circuit = Circuit::new(start_pos, &[wire1, wire2]);
+    circuit.closest_intersection()
+        .map(|intersection| intersection.pos.manhattan(start_pos) as u32)
 }
 
 pub fn get_earliest_intersection(
     start_pos: GridPoint,
-    wire1: &Vec<Move>,
-    wire2: &Vec<Move>,
+    wire1: &[Move],
+    wire2: &[Move],
 ) -> Option<u32> {
-    let grid = build_grid(start_pos, &wire1, &wire2);
-
-    grid.iter()
-        .filter(|(_, space)| {
-            if let (Some(wire1), Some(wire2)) = (space[0], space[1]) {
-                !wire1.dir.is_parallel_to(wire2.dir)
-            } else {
-                false
-            }
-        })
-        .map(|(_, space)| {
-            space[0].unwrap().dist + space[1].unwrap().dist
-        })
-        .min()
+    let circuit = Circuit::new(start_pos, &[wire1, wire2]);
+    circuit.earliest_intersection().map(|intersection| intersection.delay())
 }
 
 pub fn get_moves_from_input(file_name: &str) -> (Vec<Move>, Vec<Move>) {
-    let input = aoc2019_utils::get_input(file_name);
+    parse_input(&aoc2019_utils::get_input(file_name))
+}
+
+pub fn parse_input(input: &str) -> (Vec<Move>, Vec<Move>) {
     let (wire1, wire2) = {
         let mut lines = input.lines();
         let line1 = lines.next().unwrap();
@@ -165,6 +64,14 @@ pub fn get_moves_from_input(file_name: &str) -> (Vec<Move>, Vec<Move>) {
     (moves1, moves2)
 }
 
+/// One wire per line, for circuits with any number of them.
+pub fn parse_wires(input: &str) -> Vec<Vec<Move>> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_moves(line.trim()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -208,44 +115,6 @@ mod tests {
         ]);
     }
 
-    #[test]
-    fn test_apply_moves_to_grid() {
-        use super::*;
-
-        let moves1 = parse_moves("R1,U2,L3,D4,L1,U1,R2");
-        let moves2 = parse_moves("D1,L4");
-        let start_pos = GridPoint { x: 0, y: 0 };
-
-        let mut grid = WireGrid::new();
-
-        apply_moves_to_grid(start_pos, 0, &moves1, &mut grid);
-
-        let space_n1_1 = grid.get(&GridPoint { x: -1, y: 1});
-        assert_eq!(
-            space_n1_1,
-            Some(&[
-                Some(Wire { dir: Dir::Right, dist: 14 }),
-                None,
-            ]));
-        let space_n2_1 = grid.get(&GridPoint { x: -2, y: 1});
-        assert_eq!(
-            space_n2_1,
-            Some(&[
-                Some(Wire { dir: Dir::Down, dist: 9 }),
-                None,
-            ]));
-
-        apply_moves_to_grid(start_pos, 1, &moves2, &mut grid);
-
-        let space_n2_1 = grid.get(&GridPoint { x: -2, y: 1});
-        assert_eq!(
-            space_n2_1,
-            Some(&[
-                Some(Wire { dir: Dir::Down, dist: 9 }),
-                Some(Wire { dir: Dir::Left, dist: 3 }),
-            ]));
-    }
-
     #[test]
     fn test_get_closest_intersection() {
         use super::*;
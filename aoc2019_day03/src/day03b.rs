@@ -1,14 +1,24 @@
-pub mod day03_utils;
+use aoc2019_day03::day03_circuit;
+use aoc2019_day03::day03_render;
+use aoc2019_day03::day03_utils;
 
+use day03_circuit::*;
+use day03_render::*;
 use day03_utils::*;
 
 fn main() {
     const START_POS: GridPoint = GridPoint { x: 0, y: 0 };
 
-    let (moves1, moves2) = get_moves_from_input("inputs/day03.txt");
-    let result = get_earliest_intersection(START_POS, &moves1, &moves2);
-    match result {
-        Some(steps) => println!("steps: {}", steps),
+    let options = RenderOptions::from_args();
+    let wires = parse_wires(&aoc2019_utils::get_input("inputs/day03.txt"));
+    let circuit = Circuit::new(START_POS, &wires);
+    do_renders(&options, &circuit);
+    match circuit.earliest_intersection() {
+        Some(found) => {
+            println!("wires {} and {} cross at {}, {}",
+                found.wires[0] + 1, found.wires[1] + 1, found.pos.x, found.pos.y);
+            println!("steps: {}", found.delay());
+        },
         None => println!("no intersection"),
     }
 }
@@ -0,0 +1,36 @@
+pub mod day03_circuit;
+pub mod day03_render;
+pub mod day03_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day03_circuit::*;
+use day03_utils::*;
+
+const START_POS: GridPoint = GridPoint { x: 0, y: 0 };
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Circuit;
+
+    fn get_day(&self) -> u32 {
+        3
+    }
+
+    fn parse(&self, input: &str) -> Circuit {
+        Circuit::new(START_POS, &parse_wires(input))
+    }
+
+    fn solve_a(&self, circuit: &Circuit) -> Answer {
+        circuit.closest_intersection()
+            .map(|intersection| intersection.pos.manhattan(START_POS))
+            .into()
+    }
+
+    fn solve_b(&self, circuit: &Circuit) -> Answer {
+        circuit.earliest_intersection()
+            .map(|intersection| intersection.delay())
+            .into()
+    }
+}
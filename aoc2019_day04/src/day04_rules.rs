@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// A password rule, checked one digit at a time like a finite automaton. The
+/// state has to capture everything the rule needs to know about the digits so
+/// far, since passwords are counted by grouping those that share a state.
+pub trait Rule {
+    fn start(&self) -> u64;
+    /// The state after `digit`, or `None` if nothing starting with these
+    /// digits can pass.
+    fn next(&self, state: u64, digit: u8) -> Option<u64>;
+    fn accepts(&self, state: u64) -> bool;
+}
+
+// The previous digit, how long its run is so far and whether a run has
+// already passed, packed into one state.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct RunState {
+    last: Option<u8>,
+    run: usize,
+    found: bool,
+}
+
+impl RunState {
+    fn pack(&self) -> u64 {
+        let last = self.last.map_or(0, |digit| digit as u64 + 1);
+        last | (self.run as u64) << 4 | (self.found as u64) << 32
+    }
+
+    fn unpack(state: u64) -> Self {
+        Self {
+            last: match state & 0xf {
+                0 => None,
+                last => Some(last as u8 - 1),
+            },
+            run: ((state >> 4) & 0xfff_ffff) as usize,
+            found: (state >> 32) & 1 == 1,
+        }
+    }
+}
+
+/// Each digit is at least the one before it.
+pub struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn next(&self, state: u64, digit: u8) -> Option<u64> {
+        if state > 0 && digit < (state - 1) as u8 {
+            None
+        } else {
+            Some(digit as u64 + 1)
+        }
+    }
+
+    fn accepts(&self, _state: u64) -> bool {
+        true
+    }
+}
+
+/// Some digit repeats at least this many times in a row.
+pub struct RunAtLeast(pub usize);
+
+impl Rule for RunAtLeast {
+    fn start(&self) -> u64 {
+        RunState { last: None, run: 0, found: false }.pack()
+    }
+
+    fn next(&self, state: u64, digit: u8) -> Option<u64> {
+        let state = RunState::unpack(state);
+        let run = if state.last == Some(digit) { (state.run + 1).min(self.0) } else { 1 };
+        Some(RunState { last: Some(digit), run, found: state.found || run >= self.0 }.pack())
+    }
+
+    fn accepts(&self, state: u64) -> bool {
+        RunState::unpack(state).found
+    }
+}
+
+/// Some digit repeats exactly this many times in a row.
+pub struct RunExactly(pub usize);
+
+impl Rule for RunExactly {
+    fn start(&self) -> u64 {
+        RunState { last: None, run: 0, found: false }.pack()
+    }
+
+    fn next(&self, state: u64, digit: u8) -> Option<u64> {
+        let state = RunState::unpack(state);
+        let next = if state.last == Some(digit) {
+            RunState { run: (state.run + 1).min(self.0 + 1), ..state }
+        } else {
+            RunState { last: Some(digit), run: 1, found: state.found || state.run == self.0 }
+        };
+        Some(next.pack())
+    }
+
+    fn accepts(&self, state: u64) -> bool {
+        let state = RunState::unpack(state);
+        state.found || state.run == self.0
+    }
+}
+
+/// `digit` appears between `min` and `max` times.
+pub struct DigitCount {
+    pub digit: u8,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Rule for DigitCount {
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn next(&self, state: u64, digit: u8) -> Option<u64> {
+        let count = state + (digit == self.digit) as u64;
+        if count > self.max as u64 {
+            None
+        } else {
+            Some(count)
+        }
+    }
+
+    fn accepts(&self, state: u64) -> bool {
+        state >= self.min as u64
+    }
+}
+
+/// Every pair of neighbouring digits passes a custom check.
+pub struct Adjacent<F: Fn(u8, u8) -> bool>(pub F);
+
+impl<F: Fn(u8, u8) -> bool> Rule for Adjacent<F> {
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn next(&self, state: u64, digit: u8) -> Option<u64> {
+        if state > 0 && !(self.0)((state - 1) as u8, digit) {
+            None
+        } else {
+            Some(digit as u64 + 1)
+        }
+    }
+
+    fn accepts(&self, _state: u64) -> bool {
+        true
+    }
+}
+
+fn to_digits(num: u64) -> Vec<u8> {
+    num.to_string().bytes().map(|byte| byte - b'0').collect()
+}
+
+/// Rules that a password has to pass all of. A number's digits are the ones
+/// it's written with, so numbers of different lengths can be mixed.
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<R: Rule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn part_a() -> Self {
+        Self::new().with(NonDecreasing).with(RunAtLeast(2))
+    }
+
+    pub fn part_b() -> Self {
+        Self::new().with(NonDecreasing).with(RunExactly(2))
+    }
+
+    fn start(&self) -> Vec<u64> {
+        self.rules.iter().map(|rule| rule.start()).collect()
+    }
+
+    fn next(&self, states: &[u64], digit: u8) -> Option<Vec<u64>> {
+        self.rules.iter()
+            .zip(states)
+            .map(|(rule, state)| rule.next(*state, digit))
+            .collect()
+    }
+
+    fn accepts(&self, states: &[u64]) -> bool {
+        self.rules.iter().zip(states).all(|(rule, state)| rule.accepts(*state))
+    }
+
+    pub fn matches(&self, num: u64) -> bool {
+        to_digits(num).into_iter()
+            .try_fold(self.start(), |states, digit| self.next(&states, digit))
+            .is_some_and(|states| self.accepts(&states))
+    }
+
+    /// Counts the passwords in `range` without going through them.
+    pub fn count(&self, range: RangeInclusive<u64>) -> u64 {
+        let mut counter = Counter::new(self);
+        let below_start = match range.start() {
+            0 => 0,
+            start => counter.count_up_to(start - 1),
+        };
+        counter.count_up_to(*range.end()).saturating_sub(below_start)
+    }
+
+    /// The passwords in `range` in order, found as they're asked for.
+    pub fn passwords(&self, range: RangeInclusive<u64>) -> Passwords<'_> {
+        let mut counter = Counter::new(self);
+        let next_rank = match range.start() {
+            0 => 1,
+            start => counter.count_up_to(start - 1) + 1,
+        };
+        let last_rank = counter.count_up_to(*range.end());
+        Passwords { counter, next_rank, last_rank }
+    }
+}
+
+/// Digit DP over a rule set. Once a number's leading digits are below the
+/// bound, the rest are free, so all that matters is how many are left and
+/// what state the rules are in.
+struct Counter<'a> {
+    rules: &'a RuleSet,
+    memo: HashMap<(usize, Vec<u64>), u64>,
+}
+
+impl<'a> Counter<'a> {
+    fn new(rules: &'a RuleSet) -> Self {
+        Self { rules, memo: HashMap::new() }
+    }
+
+    /// The ways to finish with `remaining` digits of any value.
+    fn count_free(&mut self, remaining: usize, states: &[u64]) -> u64 {
+        if remaining == 0 {
+            return self.rules.accepts(states) as u64;
+        }
+        let key = (remaining, states.to_vec());
+        if let Some(count) = self.memo.get(&key) {
+            return *count;
+        }
+
+        let rules = self.rules;
+        let count = (0..=9)
+            .filter_map(|digit| rules.next(states, digit))
+            .map(|next| self.count_free(remaining - 1, &next))
+            .sum();
+        self.memo.insert(key, count);
+        count
+    }
+
+    /// The ways to finish with `remaining` digits after the leading `digit`.
+    fn count_after(&mut self, states: &[u64], digit: u8, remaining: usize) -> u64 {
+        match self.rules.next(states, digit) {
+            Some(next) => self.count_free(remaining, &next),
+            None => 0,
+        }
+    }
+
+    /// The passwords that are `len` digits long, without leading zeros.
+    fn count_len(&mut self, len: usize) -> u64 {
+        let start = self.rules.start();
+        (1..=9).map(|digit| self.count_after(&start, digit, len - 1)).sum()
+    }
+
+    /// The passwords in `0..=bound`.
+    fn count_up_to(&mut self, bound: u64) -> u64 {
+        let zero = self.rules.matches(0) as u64;
+        if bound == 0 {
+            return zero;
+        }
+
+        let digits = to_digits(bound);
+        let mut total = zero + (1..digits.len()).map(|len| self.count_len(len)).sum::<u64>();
+
+        // Numbers as long as the bound: match its leading digits, then go
+        // below it at one position and leave the rest free.
+        let mut states = Some(self.rules.start());
+        for (i, bound_digit) in digits.iter().enumerate() {
+            let prefix = match states {
+                Some(prefix) => prefix,
+                None => break,
+            };
+            let first_digit = if i == 0 { 1 } else { 0 };
+            let remaining = digits.len() - i - 1;
+            total += (first_digit..*bound_digit)
+                .map(|digit| self.count_after(&prefix, digit, remaining))
+                .sum::<u64>();
+            states = self.rules.next(&prefix, *bound_digit);
+        }
+        if states.is_some_and(|states| self.rules.accepts(&states)) {
+            total += 1;
+        }
+        total
+    }
+
+    /// The password with the given 1-based rank among all of them.
+    fn select(&mut self, mut rank: u64) -> u64 {
+        if self.rules.matches(0) {
+            if rank == 1 {
+                return 0;
+            }
+            rank -= 1;
+        }
+
+        let mut len = 1;
+        loop {
+            let count = self.count_len(len);
+            if rank <= count {
+                break;
+            }
+            rank -= count;
+            len += 1;
+        }
+
+        let mut states = self.rules.start();
+        let mut num = 0;
+        for i in 0..len {
+            let first_digit = if i == 0 { 1 } else { 0 };
+            for digit in first_digit..=9 {
+                let next = match self.rules.next(&states, digit) {
+                    Some(next) => next,
+                    None => continue,
+                };
+                let count = self.count_free(len - i - 1, &next);
+                if rank <= count {
+                    num = num * 10 + digit as u64;
+                    states = next;
+                    break;
+                }
+                rank -= count;
+            }
+        }
+        num
+    }
+}
+
+pub struct Passwords<'a> {
+    counter: Counter<'a>,
+    next_rank: u64,
+    last_rank: u64,
+}
+
+impl<'a> Iterator for Passwords<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next_rank > self.last_rank {
+            return None;
+        }
+        let num = self.counter.select(self.next_rank);
+        self.next_rank += 1;
+        Some(num)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = (self.last_rank + 1).saturating_sub(self.next_rank) as usize;
+        (left, Some(left))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(rules: &RuleSet, range: RangeInclusive<u64>) -> Vec<u64> {
+        range.filter(|num| rules.matches(*num)).collect()
+    }
+
+    #[test]
+    fn test_matches() {
+        let rules = RuleSet::part_a();
+        assert!(rules.matches(122345));
+        assert!(rules.matches(111123));
+        assert!(rules.matches(111111));
+        assert!(!rules.matches(223450));
+        assert!(!rules.matches(123789));
+
+        let rules = RuleSet::part_b();
+        assert!(rules.matches(112233));
+        assert!(!rules.matches(123444));
+        assert!(rules.matches(111122));
+        assert!(!rules.matches(223450));
+        assert!(!rules.matches(123789));
+    }
+
+    #[test]
+    fn test_count_matches_brute_force() {
+        let rule_sets = vec![
+            RuleSet::part_a(),
+            RuleSet::part_b(),
+            RuleSet::new()
+                .with(DigitCount { digit: 7, min: 1, max: 2 })
+                .with(Adjacent(|prev, next| prev != next)),
+            RuleSet::new().with(RunExactly(3)),
+        ];
+        for rules in &rule_sets {
+            for range in [0..=0, 0..=9, 5..=123, 99..=1000, 123..=135, 8000..=23456] {
+                let expected = brute_force(rules, range.clone());
+                assert_eq!(rules.count(range.clone()), expected.len() as u64);
+                assert_eq!(rules.passwords(range).collect::<Vec<u64>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_huge_range() {
+        let rules = RuleSet::part_a();
+        let count = rules.count(0..=10u64.pow(15));
+        assert!(count > 0);
+        assert_eq!(rules.count(0..=u64::MAX), rules.count(0..=(u64::MAX - 1)));
+
+        let mut passwords = rules.passwords(10u64.pow(14)..=10u64.pow(15));
+        assert_eq!(passwords.next(), Some(111111111111111));
+        assert_eq!(passwords.next(), Some(111111111111112));
+
+        // Backwards bounds make an empty range.
+        let (start, end) = (123, 122);
+        assert_eq!(rules.count(start..=end), 0);
+    }
+}
@@ -1,4 +1,4 @@
-pub mod day04_utils;
+use aoc2019_day04::day04_utils;
 
 use aoc2019_utils;
 
@@ -0,0 +1,28 @@
+pub mod day04_rules;
+pub mod day04_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day04_utils::*;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = (u64, u64);
+
+    fn get_day(&self) -> u32 {
+        4
+    }
+
+    fn parse(&self, input: &str) -> (u64, u64) {
+        extract_range(input)
+    }
+
+    fn solve_a(&self, (start, end): &(u64, u64)) -> Answer {
+        count_passwords_in_range(*start, *end).into()
+    }
+
+    fn solve_b(&self, (start, end): &(u64, u64)) -> Answer {
+        count_passwords_in_range_v2(*start, *end).into()
+    }
+}
@@ -66,6 +66,7 @@ mod tests {
         assert_eq!(cpu.exec(), true);
         assert_eq!(cpu.input, vec![]);
         assert_eq!(cpu.mem, vec![33, 0, 4, 0, 99]);
+        assert_eq!(cpu.get_output(), &[33]);
 
         // jmp to IP 0
         let prog = parse_prog("1105,1,0,99");
@@ -147,6 +148,8 @@ pub struct Cpu {
     input: Vec<i64>,
     pub mem: Vec<i64>,
     instr_ptr: usize,
+    print_output: bool,
+    output: Vec<i64>,
 }
 
 impl Cpu {
@@ -155,6 +158,8 @@ impl Cpu {
             input: vec![],
             mem: mem,
             instr_ptr: 0,
+            print_output: true,
+            output: vec![],
         }
     }
 
@@ -162,6 +167,14 @@ impl Cpu {
         self.input.push(input);
     }
 
+    pub fn get_output(&self) -> &[i64] {
+        &self.output
+    }
+
+    pub fn set_print_output(&mut self, print: bool) {
+        self.print_output = print;
+    }
+
     fn get_param_val(&mut self, modes: u32, param_num: u32) -> i64 {
         let param = self.mem[self.instr_ptr + param_num as usize];
         if (modes & (1 << (param_num - 1))) == 0 {
@@ -200,7 +213,10 @@ impl Cpu {
 
     fn do_write(&mut self, modes: u32) {
         let to_write = self.get_param_val(modes, 1);
-        println!("out> {}", to_write);
+        self.output.push(to_write);
+        if self.print_output {
+            println!("out> {}", to_write);
+        }
     }
 
     fn do_jnz(&mut self, modes: u32) -> bool {
@@ -1,4 +1,4 @@
-pub mod day05_utils;
+use aoc2019_day05::day05_utils;
 
 use aoc2019_utils;
 
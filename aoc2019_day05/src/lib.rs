@@ -0,0 +1,35 @@
+pub mod day05_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day05_utils::*;
+
+fn run_diagnostic(prog: &[i64], system_id: i64) -> Answer {
+    let mut cpu = Cpu::new(prog.to_vec());
+    cpu.set_print_output(false);
+    cpu.add_input(system_id);
+    cpu.exec_prog();
+    cpu.get_output().last().copied().into()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        5
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        run_diagnostic(prog, 1)
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        run_diagnostic(prog, 5)
+    }
+}
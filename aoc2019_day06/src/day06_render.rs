@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use aoc2019_utils::{parse_args, write_file};
+
+use crate::day06_utils::*;
+
+const PATH_COLOR: &str = "#d62728";
+
+/// Which orbit map exports to write. Both Day 6 binaries take these.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RenderOptions {
+    pub dot_file: Option<String>,
+    pub tree_file: Option<String>,
+    /// Bodies to prune the output down to. Empty means the whole map.
+    pub keep: Vec<String>,
+}
+
+impl RenderOptions {
+    pub fn from_args() -> Self {
+        let mut options = Self::default();
+        parse_args("--dot <file>, --tree <file>, --keep <body>", |arg, next_arg| {
+            match arg {
+                "--dot" => options.dot_file = Some(next_arg()),
+                "--tree" => options.tree_file = Some(next_arg()),
+                "--keep" => options.keep.push(next_arg()),
+                _ => return false,
+            }
+            true
+        });
+        options
+    }
+}
+
+/// Writes the requested exports, with the path from `YOU` to `SAN`
+/// highlighted in the DOT file if they're both in the map.
+pub fn do_renders(options: &RenderOptions, orbits: &OrbitTree) {
+    if options.dot_file.is_none() && options.tree_file.is_none() {
+        return;
+    }
+
+    let keep = options.keep.iter()
+        .map(|name| orbits.id(name).unwrap_or_else(|| panic!("no such body: {}", name)))
+        .collect::<Vec<BodyId>>();
+    let root = pruned_root(orbits, &keep);
+
+    if let Some(filename) = &options.dot_file {
+        let path = match (orbits.id("YOU"), orbits.id("SAN")) {
+            (Some(you), Some(san)) => orbits.path(you, san),
+            _ => vec![],
+        };
+        write_file(filename, &to_dot(orbits, root, &path));
+    }
+    if let Some(filename) = &options.tree_file {
+        write_file(filename, &to_tree_text(orbits, root));
+    }
+}
+
+/// The smallest subtree that has all the `keep` bodies in it, which is the
+/// whole map if there aren't any.
+pub fn pruned_root(orbits: &OrbitTree, keep: &[BodyId]) -> BodyId {
+    keep.iter()
+        .copied()
+        .reduce(|body1, body2| orbits.common_ancestor(body1, body2))
+        .unwrap_or_else(|| orbits.root())
+}
+
+// The bodies under `root`, each before the ones orbiting it and with siblings
+// in name order, paired with how deep they are below `root`. Done with a
+// stack rather than recursion, since maps can be very deep.
+fn preorder(orbits: &OrbitTree, root: BodyId) -> Vec<(BodyId, usize)> {
+    let mut result = vec![];
+    let mut stack = vec![(root, 0)];
+    while let Some((body, level)) = stack.pop() {
+        result.push((body, level));
+        stack.extend(sorted_children(orbits, body).into_iter().rev().map(|child| (child, level + 1)));
+    }
+    result
+}
+
+fn sorted_children(orbits: &OrbitTree, body: BodyId) -> Vec<BodyId> {
+    let mut children = orbits.children(body).to_vec();
+    children.sort_by_key(|child| orbits.name(*child));
+    children
+}
+
+/// Graphviz DOT for the tree under `root`, with edges going from each body to
+/// the ones orbiting it. The bodies and orbits along `path` are drawn in red.
+pub fn to_dot(orbits: &OrbitTree, root: BodyId, path: &[BodyId]) -> String {
+    let on_path = path.iter().copied().collect::<HashSet<BodyId>>();
+    let bodies = preorder(orbits, root);
+
+    let mut dot = String::from("digraph orbits {\n  rankdir=LR;\n  node [shape=circle];\n");
+    for (body, _) in &bodies {
+        if on_path.contains(body) {
+            dot.push_str(&format!("  \"{}\" [color=\"{}\", style=bold];\n",
+                orbits.name(*body), PATH_COLOR));
+        }
+    }
+    for (body, _) in bodies.iter().skip(1) {
+        let parent = orbits.parent(*body).unwrap();
+        // Two neighbours in the tree that are both on a path must be a step
+        // of it.
+        let style = if on_path.contains(body) && on_path.contains(&parent) {
+            format!(" [color=\"{}\", penwidth=3]", PATH_COLOR)
+        } else {
+            String::new()
+        };
+        dot.push_str(&format!("  \"{}\" -> \"{}\"{};\n",
+            orbits.name(parent), orbits.name(*body), style));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// An indented tree of the bodies under `root`, each with its depth in the
+/// whole map and how many bodies orbit it, directly or not.
+pub fn to_tree_text(orbits: &OrbitTree, root: BodyId) -> String {
+    preorder(orbits, root).into_iter()
+        .map(|(body, level)| {
+            format!("{}{} (depth {}, orbited by {})\n",
+                "  ".repeat(level), orbits.name(body), orbits.depth(body),
+                orbits.subtree_size(body) - 1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n";
+
+    #[test]
+    fn test_to_tree_text() {
+        let orbits = parse_input(EXAMPLE).unwrap();
+        let keep = [orbits.id("F").unwrap(), orbits.id("L").unwrap()];
+        assert_eq!(to_tree_text(&orbits, pruned_root(&orbits, &keep)), concat!(
+            "E (depth 4, orbited by 5)\n",
+            "  F (depth 5, orbited by 0)\n",
+            "  J (depth 5, orbited by 3)\n",
+            "    K (depth 6, orbited by 2)\n",
+            "      L (depth 7, orbited by 0)\n",
+            "      YOU (depth 7, orbited by 0)\n",
+        ));
+        assert_eq!(pruned_root(&orbits, &[]), orbits.root());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let orbits = parse_input(EXAMPLE).unwrap();
+        let path = orbits.path(orbits.id("YOU").unwrap(), orbits.id("SAN").unwrap());
+        let dot = to_dot(&orbits, orbits.root(), &path);
+
+        assert!(dot.starts_with("digraph orbits {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -> ").count(), orbits.len() - 1);
+        assert!(dot.contains("  \"D\" [color=\"#d62728\", style=bold];\n"));
+        assert!(dot.contains("  \"D\" -> \"I\" [color=\"#d62728\", penwidth=3];\n"));
+        assert!(dot.contains("  \"K\" -> \"YOU\" [color=\"#d62728\", penwidth=3];\n"));
+        assert!(dot.contains("  \"B\" -> \"G\";\n"));
+        assert!(dot.contains("  \"K\" -> \"L\";\n"));
+        assert!(!dot.contains("\"C\" [color"));
+    }
+}
@@ -1,121 +1,305 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 
-const ROOT_NAME: &str = "COM";
+pub const ROOT_NAME: &str = "COM";
+
+pub type BodyId = usize;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct SpaceObj {
-    name: String,
-    parent: String,
-    children: Vec<String>,
-    num_orbits: u32,
+pub enum OrbitError {
+    BadLine { line_num: usize, line: String },
+    MultipleParents { line_num: usize, body: String, parents: [String; 2] },
+    MissingRoot,
+    /// `body` orbits in a loop that never gets back to the root.
+    Cycle { body: String },
+    /// There's a separate tree under `root`.
+    Detached { root: String },
 }
 
-impl SpaceObj {
-    fn new(name: &str) -> SpaceObj {
-        SpaceObj {
-            name: name.to_string(),
-            parent: "".to_string(),
-            children: vec![],
-            num_orbits: 0,
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadLine { line_num, line } => {
+                write!(f, "line {}: expected <parent>)<body>: {}", line_num, line)
+            },
+            Self::MultipleParents { line_num, body, parents } => {
+                write!(f, "line {}: {} orbits both {} and {}",
+                    line_num, body, parents[0], parents[1])
+            },
+            Self::MissingRoot => write!(f, "there's no {}", ROOT_NAME),
+            Self::Cycle { body } => write!(f, "{} is in an orbit cycle", body),
+            Self::Detached { root } => {
+                write!(f, "{} and what orbits it aren't connected to {}", root, ROOT_NAME)
+            },
         }
     }
 }
 
-pub type OrbitSystem = HashMap<String, SpaceObj>;
+/// The orbit map as a tree, with each body's name interned to an index. The
+/// depths, subtree sizes and ancestor table are all built up front, without
+/// recursion, so queries are cheap and deep maps can't overflow the stack.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OrbitTree {
+    names: Vec<String>,
+    ids: HashMap<String, BodyId>,
+    parents: Vec<Option<BodyId>>,
+    children: Vec<Vec<BodyId>>,
+    depths: Vec<u32>,
+    subtree_sizes: Vec<u32>,
+    /// `ancestors[k][body]` is `body`'s ancestor 2^k levels up, or the root if
+    /// that's too far.
+    ancestors: Vec<Vec<BodyId>>,
+}
+
+fn intern(ids: &mut HashMap<String, BodyId>, names: &mut Vec<String>, name: &str) -> BodyId {
+    if let Some(id) = ids.get(name) {
+        return *id;
+    }
+    names.push(name.to_string());
+    ids.insert(name.to_string(), names.len() - 1);
+    names.len() - 1
+}
 
-fn do_calc_orbit_nums(orbits: &mut OrbitSystem, name: &str, dist: u32) {
-    let children: Vec<String>;
+impl OrbitTree {
+    pub fn parse(input: &str) -> Result<Self, OrbitError> {
+        let mut names = vec![];
+        let mut ids = HashMap::new();
+        let mut parents: Vec<Option<BodyId>> = vec![];
 
-    {
-        let mut parent = orbits.get_mut(name).unwrap();
-        parent.num_orbits = dist;
-        children = parent.children.clone();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let bad_line = || OrbitError::BadLine { line_num: i + 1, line: line.to_string() };
+            let mut parts = line.split(')');
+            let (parent, body) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(parent), Some(body), None) if !parent.is_empty() && !body.is_empty() => {
+                    (parent, body)
+                },
+                _ => return Err(bad_line()),
+            };
+
+            let parent = intern(&mut ids, &mut names, parent);
+            let body = intern(&mut ids, &mut names, body);
+            parents.resize(names.len(), None);
+            if let Some(other) = parents[body] {
+                return Err(OrbitError::MultipleParents {
+                    line_num: i + 1,
+                    body: names[body].clone(),
+                    parents: [names[other].clone(), names[parent].clone()],
+                });
+            }
+            parents[body] = Some(parent);
+        }
+
+        let root = *ids.get(ROOT_NAME).ok_or(OrbitError::MissingRoot)?;
+        if parents[root].is_some() {
+            return Err(Self::find_unrooted(&names, &parents, root));
+        }
+
+        let mut children = vec![vec![]; names.len()];
+        parents.iter().enumerate().for_each(|(body, parent)| {
+            if let Some(parent) = parent {
+                children[*parent].push(body);
+            }
+        });
+
+        // Breadth first from the root, so each body comes after its parent.
+        let mut depths = vec![0; names.len()];
+        let mut order = vec![root];
+        let mut queue = VecDeque::from(vec![root]);
+        while let Some(body) = queue.pop_front() {
+            for child in &children[body] {
+                depths[*child] = depths[body] + 1;
+                order.push(*child);
+                queue.push_back(*child);
+            }
+        }
+
+        if order.len() < names.len() {
+            let reached = order.iter().copied().collect::<HashSet<BodyId>>();
+            let body = (0..names.len()).find(|body| !reached.contains(body)).unwrap();
+            return Err(Self::find_unrooted(&names, &parents, body));
+        }
+
+        let mut subtree_sizes = vec![1; names.len()];
+        order.iter().rev().for_each(|body| {
+            if let Some(parent) = parents[*body] {
+                subtree_sizes[parent] += subtree_sizes[*body];
+            }
+        });
+
+        let max_depth = depths.iter().copied().max().unwrap_or(0);
+        let mut ancestors = vec![
+            parents.iter().map(|parent| parent.unwrap_or(root)).collect::<Vec<BodyId>>(),
+        ];
+        while (1 << ancestors.len()) <= max_depth {
+            let prev = ancestors.last().unwrap();
+            let next = prev.iter().map(|ancestor| prev[*ancestor]).collect();
+            ancestors.push(next);
+        }
+
+        Ok(Self { names, ids, parents, children, depths, subtree_sizes, ancestors })
     }
 
-    for child_name in children {
-        do_calc_orbit_nums(orbits, &child_name, dist + 1);
+    // Follows the parents up from a body that the root doesn't reach, to say
+    // whether it's stuck in a cycle or under some other root.
+    fn find_unrooted(names: &[String], parents: &[Option<BodyId>], body: BodyId) -> OrbitError {
+        let mut seen = HashSet::new();
+        let mut current = body;
+        while seen.insert(current) {
+            match parents[current] {
+                Some(parent) => current = parent,
+                None => return OrbitError::Detached { root: names[current].clone() },
+            }
+        }
+        OrbitError::Cycle { body: names[current].clone() }
     }
-}
 
-fn calc_orbit_nums(orbits: &mut OrbitSystem) {
-    do_calc_orbit_nums(orbits, ROOT_NAME, 0);
-}
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
 
-fn get_parent_chain(orbits: &OrbitSystem, name: &str) -> Vec<String> {
-    let parent = &orbits.get(name).unwrap().parent;
-    let mut chain = vec![name.to_string()];
-    if parent != "" {
-        let parent_chain = get_parent_chain(orbits, parent);
-        chain.extend_from_slice(&parent_chain[..]);
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<BodyId> {
+        self.ids.get(name).copied()
     }
-    chain
-}
 
-pub fn get_num_transfers(orbits: &OrbitSystem, obj1: &str, obj2: &str) -> u32 {
-    let mut chain1 = get_parent_chain(orbits, obj1);
-    let mut chain2 = get_parent_chain(orbits, obj2);
+    pub fn name(&self, body: BodyId) -> &str {
+        &self.names[body]
+    }
 
-    while !chain1.is_empty()
-        && !chain2.is_empty()
-        && chain1.last() == chain2.last()
-    {
-        chain1.pop();
-        chain2.pop();
+    pub fn root(&self) -> BodyId {
+        self.ids[ROOT_NAME]
     }
 
-    (chain1.len() + chain2.len() - 2) as u32
-}
+    pub fn parent(&self, body: BodyId) -> Option<BodyId> {
+        self.parents[body]
+    }
+
+    pub fn children(&self, body: BodyId) -> &[BodyId] {
+        &self.children[body]
+    }
+
+    /// How many bodies `body` orbits, directly or not.
+    pub fn depth(&self, body: BodyId) -> u32 {
+        self.depths[body]
+    }
+
+    /// How many bodies are in the tree under `body`, counting itself.
+    pub fn subtree_size(&self, body: BodyId) -> u32 {
+        self.subtree_sizes[body]
+    }
 
-pub fn parse_input(input: &str) -> OrbitSystem {
-    let mut sys = OrbitSystem::new();
+    pub fn total_orbits(&self) -> u64 {
+        self.depths.iter().map(|depth| *depth as u64).sum()
+    }
 
-    // scan once just for objects
-    input.lines().for_each(|line| {
-        let mut parts = line.split(")"); 
-        let parent = parts.next().unwrap();
-        if parent == ROOT_NAME {
-            sys.insert(parent.to_string(), SpaceObj::new(parent));
+    /// The body `levels` up from `body`, if it's not past the root.
+    pub fn ancestor(&self, body: BodyId, levels: u32) -> Option<BodyId> {
+        if levels > self.depths[body] {
+            return None;
+        }
+        Some((0..self.ancestors.len())
+            .filter(|k| levels & (1 << k) != 0)
+            .fold(body, |current, k| self.ancestors[k][current]))
+    }
+
+    /// The deepest body that both bodies orbit, or are.
+    pub fn common_ancestor(&self, body1: BodyId, body2: BodyId) -> BodyId {
+        let (deep, shallow) = if self.depths[body1] >= self.depths[body2] {
+            (body1, body2)
+        } else {
+            (body2, body1)
+        };
+        let mut deep = self.ancestor(deep, self.depths[deep] - self.depths[shallow]).unwrap();
+        let mut shallow = shallow;
+        if deep == shallow {
+            return deep;
         }
-        let child = parts.next().unwrap();
-        sys.insert(child.to_string(), SpaceObj::new(child));
-    });
-
-    // now do parent/child connections
-    input.lines().for_each(|line| {
-        let mut parts = line.split(")"); 
-        let parent = parts.next().unwrap();
-        let child = parts.next().unwrap();
-        
-        sys.get_mut(child).unwrap().parent = parent.to_string();
-        sys.get_mut(parent).unwrap().children.push(child.to_string());
-    });
-
-    calc_orbit_nums(&mut sys);
-
-    sys
-}
 
-fn do_get_total_orbits(orbits: &OrbitSystem, obj_name: &str) -> u32 {
-    let obj = orbits.get(obj_name).unwrap();
-    let mut num_orbits = obj.num_orbits;
+        for k in (0..self.ancestors.len()).rev() {
+            if self.ancestors[k][deep] != self.ancestors[k][shallow] {
+                deep = self.ancestors[k][deep];
+                shallow = self.ancestors[k][shallow];
+            }
+        }
+        self.ancestors[0][deep]
+    }
+
+    /// How many orbits apart two bodies are.
+    pub fn distance(&self, body1: BodyId, body2: BodyId) -> u32 {
+        let common = self.common_ancestor(body1, body2);
+        self.depths[body1] + self.depths[body2] - 2 * self.depths[common]
+    }
 
-    for child_name in &obj.children {
-        num_orbits += do_get_total_orbits(orbits, &child_name);
+    /// The orbital transfers to get from what `from` orbits to what `to`
+    /// orbits, as in the puzzle. The root doesn't orbit anything.
+    pub fn num_transfers(&self, from: BodyId, to: BodyId) -> Option<u32> {
+        Some(self.distance(self.parent(from)?, self.parent(to)?))
     }
 
-    num_orbits
+    /// The bodies from `from` to `to`, both included, going through their
+    /// common ancestor.
+    pub fn path(&self, from: BodyId, to: BodyId) -> Vec<BodyId> {
+        let common = self.common_ancestor(from, to);
+        let up_chain = |body: BodyId| {
+            let mut chain = vec![body];
+            let mut current = body;
+            while current != common {
+                current = self.parents[current].unwrap();
+                chain.push(current);
+            }
+            chain
+        };
+
+        let mut path = up_chain(from);
+        let mut down = up_chain(to);
+        down.pop();
+        path.extend(down.into_iter().rev());
+        path
+    }
 }
 
-pub fn get_total_orbits(orbits: &OrbitSystem) -> u32 {
-    do_get_total_orbits(orbits, ROOT_NAME)
+pub fn parse_input(input: &str) -> Result<OrbitTree, OrbitError> {
+    OrbitTree::parse(input)
+}
+
+pub fn get_total_orbits(orbits: &OrbitTree) -> u64 {
+    orbits.total_orbits()
+}
+
+pub fn get_num_transfers(orbits: &OrbitTree, obj1: &str, obj2: &str) -> Option<u32> {
+    orbits.num_transfers(orbits.id(obj1)?, orbits.id(obj2)?)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "COM)B\n",
+        "B)C\n",
+        "C)D\n",
+        "D)E\n",
+        "E)F\n",
+        "B)G\n",
+        "G)H\n",
+        "D)I\n",
+        "E)J\n",
+        "J)K\n",
+        "K)L\n",
+        "K)YOU\n",
+        "I)SAN\n",
+    );
+
     #[test]
     fn test_orbits_basics() {
-        use super::*;
-
         let input = concat!(
             "D)E\n",
             "E)F\n",
@@ -130,29 +314,28 @@ mod tests {
             "K)L\n",
         );
 
-        let orbit_sys = parse_input(&input);
+        let orbit_sys = parse_input(input).unwrap();
         assert_eq!(orbit_sys.len(), 12);
+        let id = |name| orbit_sys.id(name).unwrap();
+        let names = |bodies: &[BodyId]| {
+            bodies.iter().map(|body| orbit_sys.name(*body)).collect::<Vec<&str>>()
+        };
+
+        let obj = id("COM");
+        assert_eq!(obj, orbit_sys.root());
+        assert_eq!(orbit_sys.depth(obj), 0);
+        assert_eq!(orbit_sys.parent(obj), None);
+        assert_eq!(names(orbit_sys.children(obj)), vec!["B"]);
 
-        let obj = orbit_sys.get("COM").unwrap();
-        assert_eq!(obj.name, "COM");
-        assert_eq!(obj.num_orbits, 0);
-        assert_eq!(obj.parent, "");
-        assert_eq!(obj.children.len(), 1);
-        assert_eq!(obj.children.contains(&"B".to_string()), true);
-
-        let obj = orbit_sys.get("B").unwrap();
-        assert_eq!(obj.name, "B");
-        assert_eq!(obj.num_orbits, 1);
-        assert_eq!(obj.parent, "COM");
-        assert_eq!(obj.children.len(), 2);
-        assert_eq!(obj.children.contains(&"C".to_string()), true);
-        assert_eq!(obj.children.contains(&"G".to_string()), true);
-
-        let obj = orbit_sys.get("L").unwrap();
-        assert_eq!(obj.name, "L");
-        assert_eq!(obj.num_orbits, 7);
-        assert_eq!(obj.parent, "K");
-        assert_eq!(obj.children.len(), 0);
+        let obj = id("B");
+        assert_eq!(orbit_sys.depth(obj), 1);
+        assert_eq!(orbit_sys.parent(obj), Some(id("COM")));
+        assert_eq!(names(orbit_sys.children(obj)), vec!["C", "G"]);
+
+        let obj = id("L");
+        assert_eq!(orbit_sys.depth(obj), 7);
+        assert_eq!(orbit_sys.parent(obj), Some(id("K")));
+        assert_eq!(orbit_sys.children(obj).len(), 0);
 
         let result = get_total_orbits(&orbit_sys);
         assert_eq!(result, 42);
@@ -160,26 +343,64 @@ mod tests {
 
     #[test]
     fn test_get_num_transfers() {
-        use super::*;
+        let orbit_sys = parse_input(EXAMPLE).unwrap();
+        let result = get_num_transfers(&orbit_sys, "YOU", "SAN");
+        assert_eq!(result, Some(4));
+        assert_eq!(get_num_transfers(&orbit_sys, "COM", "SAN"), None);
+    }
 
-        let input = concat!(
-            "COM)B\n",
-            "B)C\n",
-            "C)D\n",
-            "D)E\n",
-            "E)F\n",
-            "B)G\n",
-            "G)H\n",
-            "D)I\n",
-            "E)J\n",
-            "J)K\n",
-            "K)L\n",
-            "K)YOU\n",
-            "I)SAN\n",
-        );
+    #[test]
+    fn test_queries() {
+        let orbit_sys = parse_input(EXAMPLE).unwrap();
+        let id = |name| orbit_sys.id(name).unwrap();
 
-        let orbit_sys = parse_input(&input);
-        let result = get_num_transfers(&orbit_sys, "YOU", "SAN");
-        assert_eq!(result, 4);
+        assert_eq!(orbit_sys.subtree_size(id("COM")), 14);
+        assert_eq!(orbit_sys.subtree_size(id("D")), 9);
+        assert_eq!(orbit_sys.subtree_size(id("SAN")), 1);
+
+        assert_eq!(orbit_sys.ancestor(id("YOU"), 3), Some(id("E")));
+        assert_eq!(orbit_sys.ancestor(id("YOU"), 7), Some(id("COM")));
+        assert_eq!(orbit_sys.ancestor(id("YOU"), 8), None);
+
+        assert_eq!(orbit_sys.common_ancestor(id("YOU"), id("SAN")), id("D"));
+        assert_eq!(orbit_sys.common_ancestor(id("L"), id("E")), id("E"));
+        assert_eq!(orbit_sys.common_ancestor(id("H"), id("F")), id("B"));
+        assert_eq!(orbit_sys.distance(id("H"), id("F")), 6);
+
+        let path = orbit_sys.path(id("YOU"), id("SAN")).into_iter()
+            .map(|body| orbit_sys.name(body))
+            .collect::<Vec<&str>>();
+        assert_eq!(path, vec!["YOU", "K", "J", "E", "D", "I", "SAN"]);
+        assert_eq!(orbit_sys.path(id("C"), id("C")), vec![id("C")]);
+    }
+
+    #[test]
+    fn test_deep_map() {
+        let input = std::iter::once("COM)0".to_string())
+            .chain((1..200_000).map(|i| format!("{}){}", i - 1, i)))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let orbit_sys = parse_input(&input).unwrap();
+        assert_eq!(orbit_sys.depth(orbit_sys.id("199999").unwrap()), 200_000);
+        assert_eq!(get_num_transfers(&orbit_sys, "199999", "5"), Some(199_994));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input("COM)B\nB\n"),
+            Err(OrbitError::BadLine { line_num: 2, line: "B".to_string() }));
+        assert_eq!(parse_input("COM)B\nCOM)C\nB)D\nC)D\n"),
+            Err(OrbitError::MultipleParents {
+                line_num: 4,
+                body: "D".to_string(),
+                parents: ["B".to_string(), "C".to_string()],
+            }));
+        assert_eq!(parse_input("A)B\n"), Err(OrbitError::MissingRoot));
+        assert_eq!(parse_input("COM)B\nC)D\nD)C\n"),
+            Err(OrbitError::Cycle { body: "C".to_string() }));
+        assert_eq!(parse_input("COM)B\nX)Y\n"),
+            Err(OrbitError::Detached { root: "X".to_string() }));
+        assert_eq!(parse_input("COM)B\nB)COM\n"),
+            Err(OrbitError::Cycle { body: "COM".to_string() }));
     }
 }
@@ -1,12 +1,16 @@
-pub mod day06_utils;
+use aoc2019_day06::day06_render;
+use aoc2019_day06::day06_utils;
 
 use aoc2019_utils;
 
+use day06_render::*;
 use day06_utils::*;
 
 fn main() {
+    let options = RenderOptions::from_args();
     let input = aoc2019_utils::get_input("inputs/day06.txt");
-    let orbit_sys = parse_input(&input);
+    let orbit_sys = parse_input(&input).unwrap_or_else(|err| panic!("bad orbit map: {}", err));
+    do_renders(&options, &orbit_sys);
     let num_orbits = get_total_orbits(&orbit_sys);
     println!("total orbits: {}", num_orbits);
 }
@@ -1,12 +1,18 @@
-pub mod day06_utils;
+use aoc2019_day06::day06_render;
+use aoc2019_day06::day06_utils;
 
 use aoc2019_utils;
 
+use day06_render::*;
 use day06_utils::*;
 
 fn main() {
+    let options = RenderOptions::from_args();
     let input = aoc2019_utils::get_input("inputs/day06.txt");
-    let orbit_sys = parse_input(&input);
-    let num_transfers = get_num_transfers(&orbit_sys, "YOU", "SAN");
-    println!("num transfers: {}", num_transfers);
+    let orbit_sys = parse_input(&input).unwrap_or_else(|err| panic!("bad orbit map: {}", err));
+    do_renders(&options, &orbit_sys);
+    match get_num_transfers(&orbit_sys, "YOU", "SAN") {
+        Some(num_transfers) => println!("num transfers: {}", num_transfers),
+        None => println!("YOU and SAN need to be orbiting something"),
+    }
 }
@@ -0,0 +1,34 @@
+pub mod day06_render;
+pub mod day06_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day06_utils::*;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Result<OrbitTree, OrbitError>;
+
+    fn get_day(&self) -> u32 {
+        6
+    }
+
+    fn parse(&self, input: &str) -> Result<OrbitTree, OrbitError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, orbits: &Result<OrbitTree, OrbitError>) -> Answer {
+        match orbits {
+            Ok(orbits) => get_total_orbits(orbits).into(),
+            Err(err) => err.to_string().into(),
+        }
+    }
+
+    fn solve_b(&self, orbits: &Result<OrbitTree, OrbitError>) -> Answer {
+        match orbits {
+            Ok(orbits) => get_num_transfers(orbits, "YOU", "SAN").into(),
+            Err(err) => err.to_string().into(),
+        }
+    }
+}
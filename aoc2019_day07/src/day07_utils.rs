@@ -1,36 +1,35 @@
-use std::convert::TryInto;
+use std::sync::Mutex;
+use std::thread;
 
-use aoc2019_utils::*;
+use aoc2019_utils::permutations::*;
 use crate::day07_cpu::*;
 
-pub fn permute_list(list: &Vec<i64>) -> Vec<Vec<i64>> {
-
-    if list.is_empty() {
-        return vec![vec![]];
-    }
-
-    let num_results = fact(list.len().try_into().unwrap());
-    let mut results = Vec::with_capacity(num_results as usize);
+/// How the amplifiers are wired up. In a chain each one runs once and the
+/// last one's output is the signal. In a feedback loop the last one's output
+/// goes back to the first, until the last one halts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Topology {
+    Chain,
+    Feedback,
+}
 
-    for i in 0..list.len() {
-        let num = list[i];
-        let other_nums = {
-            let mut other_nums = list.clone();
-            other_nums.remove(i);
-            other_nums
-        };
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PhaseResult {
+    pub phases: Vec<i64>,
+    pub signal: i64,
+}
 
-        let others_permuted = permute_list(&other_nums);
-        for other in others_permuted {
-            results.push(vec![num]);
-            results.last_mut().unwrap().extend_from_slice(&other[..]);
-        }
+impl PhaseResult {
+    // Ties go to the lowest phase sequence, so the result doesn't depend on
+    // which thread found what.
+    fn is_better_than(&self, other: &Self) -> bool {
+        self.signal > other.signal || (self.signal == other.signal && self.phases < other.phases)
     }
-
-    results
 }
 
-fn run_prog_chain(prog: &Vec<i64>, phases: &Vec<i64>) -> i64 {
+/// The signal out of one amplifier per phase, or `None` if there are no
+/// amplifiers or they stall waiting on each other.
+pub fn run_amplifiers(prog: &Vec<i64>, phases: &[i64], topology: Topology) -> Option<i64> {
     let mut cpus: Vec<Cpu> = phases.iter()
         .map(|phase| {
             let mut cpu = Cpu::new(prog);
@@ -39,33 +38,69 @@ fn run_prog_chain(prog: &Vec<i64>, phases: &Vec<i64>) -> i64 {
             cpu
         })
         .collect();
+    cpus.first_mut()?.add_input(0);
 
-    cpus.first_mut().unwrap().add_input(0);
-
-    let mut signal = 0;
     let num_cpus = cpus.len();
-    while cpus.last().unwrap().get_state() != CpuState::Done {
+    let mut signal = None;
+    loop {
+        let mut has_output = false;
         for i in 0..num_cpus {
-            let cpu = &mut cpus[i];
-            cpu.exec_prog();
-            let outval = cpu.pop_output().unwrap();
-            signal = outval;
+            cpus[i].exec_prog();
+            while let Some(outval) = cpus[i].pop_output() {
+                has_output = true;
+                if i == num_cpus - 1 {
+                    signal = Some(outval);
+                    if topology == Topology::Chain {
+                        continue;
+                    }
+                }
+                cpus[(i + 1) % num_cpus].add_input(outval);
+            }
+        }
 
-            let next_cpu = &mut cpus[(i + 1) % num_cpus];
-            next_cpu.add_input(outval);
+        if topology == Topology::Chain || cpus[num_cpus - 1].get_state() == CpuState::Done {
+            return signal;
+        }
+        if !has_output {
+            return None;
         }
     }
+}
 
-    signal
+pub fn default_num_threads() -> usize {
+    thread::available_parallelism().map(|num| num.get()).unwrap_or(1)
 }
 
-pub fn find_max_of_all_phase_combos(prog: &Vec<i64>, phases: &Vec<i64>) -> i64 {
-    let phases_list = permute_list(phases);
-    phases_list.iter().map(|phases| {
-        run_prog_chain(prog, phases)
+/// Tries every ordering of `phases` across `num_threads` threads, which take
+/// the orderings from a shared iterator as they go, and returns the one with
+/// the highest signal.
+pub fn find_best_phases(prog: &Vec<i64>, phases: &[i64], topology: Topology, num_threads: usize)
+-> Option<PhaseResult> {
+    let orderings = Mutex::new(permutations(phases));
+    let worker = || {
+        let mut best: Option<PhaseResult> = None;
+        loop {
+            let phases = match orderings.lock().unwrap().next() {
+                Some(phases) => phases,
+                None => return best,
+            };
+            if let Some(signal) = run_amplifiers(prog, &phases, topology) {
+                let result = PhaseResult { phases, signal };
+                if best.as_ref().is_none_or(|best| result.is_better_than(best)) {
+                    best = Some(result);
+                }
+            }
+        }
+    };
+
+    thread::scope(|scope| {
+        let handles = (0..num_threads.max(1))
+            .map(|_| scope.spawn(worker))
+            .collect::<Vec<_>>();
+        handles.into_iter()
+            .filter_map(|handle| handle.join().unwrap())
+            .reduce(|best, result| if result.is_better_than(&best) { result } else { best })
     })
-    .max()
-    .unwrap()
 }
 
 #[cfg(test)]
@@ -73,75 +108,39 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_permute_list() {
-
-        let result = permute_list(&vec![1, 2, 3, 4]);
-        let expected = vec!(
-            vec![1, 2, 3, 4],
-            vec![1, 2, 4, 3],
-            vec![1, 3, 2, 4],
-            vec![1, 3, 4, 2],
-            vec![1, 4, 2, 3],
-            vec![1, 4, 3, 2],
-
-            vec![2, 1, 3, 4],
-            vec![2, 1, 4, 3],
-            vec![2, 3, 1, 4],
-            vec![2, 3, 4, 1],
-            vec![2, 4, 1, 3],
-            vec![2, 4, 3, 1],
-
-            vec![3, 1, 2, 4],
-            vec![3, 1, 4, 2],
-            vec![3, 2, 1, 4],
-            vec![3, 2, 4, 1],
-            vec![3, 4, 1, 2],
-            vec![3, 4, 2, 1],
-
-            vec![4, 1, 2, 3],
-            vec![4, 1, 3, 2],
-            vec![4, 2, 1, 3],
-            vec![4, 2, 3, 1],
-            vec![4, 3, 1, 2],
-            vec![4, 3, 2, 1],
-        );
-        assert_eq!(result, expected);
-    }
-
-    #[test]
-    fn test_find_max_of_all_phase_combos() {
-        let phases = vec![0, 1, 2, 3, 4];
+    fn test_find_best_phases() {
+        let phases = [0, 1, 2, 3, 4];
+        let best = |prog: &Vec<i64>, phases: &[i64], topology| {
+            let result = find_best_phases(prog, phases, topology, 4).unwrap();
+            (result.signal, result.phases)
+        };
 
         let prog_str = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0";
         let prog = parse_prog(&prog_str);
-        let result = find_max_of_all_phase_combos(&prog, &phases);
-        assert_eq!(result, 43210);
+        assert_eq!(best(&prog, &phases, Topology::Chain), (43210, vec![4, 3, 2, 1, 0]));
 
         let prog_str = concat!(
             "3,23,3,24,1002,24,10,24,1002,23,-1,23,",
             "101,5,23,23,1,24,23,23,4,23,99,0,0",
         );
         let prog = parse_prog(&prog_str);
-        let result = find_max_of_all_phase_combos(&prog, &phases);
-        assert_eq!(result, 54321);
+        assert_eq!(best(&prog, &phases, Topology::Chain), (54321, vec![0, 1, 2, 3, 4]));
 
         let prog_str = concat!(
             "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,",
             "1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
         );
         let prog = parse_prog(&prog_str);
-        let result = find_max_of_all_phase_combos(&prog, &phases);
-        assert_eq!(result, 65210);
+        assert_eq!(best(&prog, &phases, Topology::Chain), (65210, vec![1, 0, 4, 3, 2]));
 
-        let phases = vec![5, 6, 7, 8, 9];
+        let phases = [5, 6, 7, 8, 9];
 
         let prog_str = concat!(
             "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,",
             "27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
         );
         let prog = parse_prog(&prog_str);
-        let result = find_max_of_all_phase_combos(&prog, &phases);
-        assert_eq!(result, 139629729);
+        assert_eq!(best(&prog, &phases, Topology::Feedback), (139629729, vec![9, 8, 7, 6, 5]));
 
         let prog_str = concat!(
             "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,",
@@ -149,7 +148,26 @@ mod tests {
             "53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
         );
         let prog = parse_prog(&prog_str);
-        let result = find_max_of_all_phase_combos(&prog, &phases);
-        assert_eq!(result, 18216);
+        assert_eq!(best(&prog, &phases, Topology::Feedback), (18216, vec![9, 7, 8, 5, 6]));
+    }
+
+    #[test]
+    fn test_amplifier_counts() {
+        let prog = parse_prog(concat!(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,",
+            "27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        ));
+        assert_eq!(run_amplifiers(&prog, &[9, 8, 7, 6, 5], Topology::Feedback), Some(139629729));
+        assert_eq!(run_amplifiers(&prog, &[], Topology::Feedback), None);
+
+        let phases = [5, 6, 7];
+        let serial = find_best_phases(&prog, &phases, Topology::Feedback, 1);
+        assert_eq!(find_best_phases(&prog, &phases, Topology::Feedback, 8), serial);
+        assert_eq!(serial.unwrap().phases.len(), 3);
+
+        // A chain stops after one pass, where the feedback loop would keep going.
+        let prog = parse_prog("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+        assert_eq!(run_amplifiers(&prog, &[1, 2, 3, 4, 5, 6, 7], Topology::Chain),
+            Some(1234567));
     }
 }
@@ -1,5 +1,5 @@
-pub mod day07_cpu;
-pub mod day07_utils;
+use aoc2019_day07::day07_cpu;
+use aoc2019_day07::day07_utils;
 
 use aoc2019_utils;
 
@@ -9,6 +9,14 @@ use day07_utils::*;
 fn main() {
     let input = aoc2019_utils::get_input("inputs/day07.txt");
     let prog = parse_prog(&input);
-    let max_signal = find_max_of_all_phase_combos(&prog, &vec![5, 6, 7, 8, 9]);
-    println!("max signal: {}", max_signal);
+    match find_best_phases(&prog, &[5, 6, 7, 8, 9], Topology::Feedback, default_num_threads()) {
+        Some(best) => {
+            let phases = best.phases.iter()
+                .map(|phase| phase.to_string())
+                .collect::<Vec<String>>();
+            println!("phases: {}", phases.join(","));
+            println!("max signal: {}", best.signal);
+        },
+        None => println!("no phase sequence gave a signal"),
+    }
 }
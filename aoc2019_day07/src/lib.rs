@@ -0,0 +1,33 @@
+pub mod day07_cpu;
+pub mod day07_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day07_cpu::*;
+use day07_utils::*;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        7
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        find_best_phases(prog, &[0, 1, 2, 3, 4], Topology::Chain, default_num_threads())
+            .map(|best| best.signal)
+            .into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        find_best_phases(prog, &[5, 6, 7, 8, 9], Topology::Feedback, default_num_threads())
+            .map(|best| best.signal)
+            .into()
+    }
+}
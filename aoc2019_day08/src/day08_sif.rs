@@ -0,0 +1,240 @@
+use std::fmt;
+
+use aoc2019_utils::grid::Grid;
+
+pub const BLACK: u8 = 0;
+pub const WHITE: u8 = 1;
+pub const TRANSPARENT: u8 = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SifError {
+    EmptySize,
+    NoLayers,
+    BadDigit { pos: usize, c: char },
+    BadLength { len: usize, layer_len: usize },
+    BadColor { layer: usize, x: usize, y: usize, digit: u8 },
+    LayerSize { layer: usize, width: usize, height: usize },
+}
+
+impl fmt::Display for SifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptySize => write!(f, "an image needs a width and height of at least 1"),
+            Self::NoLayers => write!(f, "an image needs at least one layer"),
+            Self::BadDigit { pos, c } => write!(f, "'{}' at {} isn't a digit", c, pos),
+            Self::BadLength { len, layer_len } => {
+                write!(f, "{} digits don't make whole layers of {}", len, layer_len)
+            },
+            Self::BadColor { layer, x, y, digit } => {
+                write!(f, "layer {} has an unknown color {} at {}, {}", layer, digit, x, y)
+            },
+            Self::LayerSize { layer, width, height } => {
+                write!(f, "layer {} is {}x{} but the first layer isn't", layer, width, height)
+            },
+        }
+    }
+}
+
+/// One layer of digits. Only `BLACK`, `WHITE` and `TRANSPARENT` are colors,
+/// but any digit can be decoded.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Layer {
+    pub pixels: Grid<u8>,
+}
+
+impl Layer {
+    pub fn count(&self, digit: u8) -> usize {
+        self.pixels.cells().iter().filter(|pix| **pix == digit).count()
+    }
+}
+
+/// An image in the Space Image Format. The first layer is in front.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub layers: Vec<Layer>,
+}
+
+impl Image {
+    pub fn decode(data: &str, width: usize, height: usize) -> Result<Self, SifError> {
+        if width == 0 || height == 0 {
+            return Err(SifError::EmptySize);
+        }
+
+        let digits = data.trim_end().chars()
+            .enumerate()
+            .map(|(pos, c)| {
+                c.to_digit(10).map(|digit| digit as u8).ok_or(SifError::BadDigit { pos, c })
+            })
+            .collect::<Result<Vec<u8>, SifError>>()?;
+
+        let layer_len = width * height;
+        if digits.is_empty() {
+            return Err(SifError::NoLayers);
+        }
+        if digits.len() % layer_len != 0 {
+            return Err(SifError::BadLength { len: digits.len(), layer_len });
+        }
+
+        let layers = digits.chunks(layer_len)
+            .map(|chunk| Layer { pixels: Grid::from_cells(width, chunk.to_vec()).unwrap() })
+            .collect();
+        Ok(Self { width, height, layers })
+    }
+
+    pub fn encode(&self) -> String {
+        self.layers.iter()
+            .flat_map(|layer| layer.pixels.cells())
+            .map(|digit| (b'0' + digit) as char)
+            .collect()
+    }
+
+    /// Builds an image from bitmaps, front first, where `None` is transparent.
+    pub fn from_bitmaps(bitmaps: &[Grid<Option<bool>>]) -> Result<Self, SifError> {
+        let first = bitmaps.first().ok_or(SifError::NoLayers)?;
+        let (width, height) = (first.width(), first.height());
+        if width == 0 || height == 0 {
+            return Err(SifError::EmptySize);
+        }
+
+        let layers = bitmaps.iter()
+            .enumerate()
+            .map(|(layer, bitmap)| {
+                if bitmap.width() != width || bitmap.height() != height {
+                    return Err(SifError::LayerSize {
+                        layer,
+                        width: bitmap.width(),
+                        height: bitmap.height(),
+                    });
+                }
+                let pixels = bitmap.map(|pix| match pix {
+                    Some(false) => BLACK,
+                    Some(true) => WHITE,
+                    None => TRANSPARENT,
+                });
+                Ok(Layer { pixels })
+            })
+            .collect::<Result<Vec<Layer>, SifError>>()?;
+        Ok(Self { width, height, layers })
+    }
+
+    /// Stacks the layers, with `true` for white pixels and `None` where every
+    /// layer is transparent.
+    pub fn composite(&self) -> Result<Grid<Option<bool>>, SifError> {
+        let mut result = Grid::new(self.width, self.height, None);
+        for (layer_num, layer) in self.layers.iter().enumerate().rev() {
+            for (coord, digit) in layer.pixels.iter() {
+                result[coord] = match *digit {
+                    BLACK => Some(false),
+                    WHITE => Some(true),
+                    TRANSPARENT => result[coord],
+                    digit => {
+                        return Err(SifError::BadColor {
+                            layer: layer_num,
+                            x: coord.x as usize,
+                            y: coord.y as usize,
+                            digit,
+                        });
+                    },
+                };
+            }
+        }
+        Ok(result)
+    }
+
+    /// The image that's actually shown. Pixels that are transparent all the
+    /// way down show the black of space.
+    pub fn flatten(&self) -> Result<Grid<bool>, SifError> {
+        Ok(self.composite()?.map(|pix| pix.unwrap_or(false)))
+    }
+}
+
+/// Plain (P1) PBM. Ink is black, so white pixels are written as 0.
+pub fn to_pbm(pixels: &Grid<bool>) -> String {
+    let mut txt = format!("P1\n{} {}\n", pixels.width(), pixels.height());
+    pixels.rows().for_each(|row| {
+        let row = row.iter()
+            .map(|pix| if *pix { "0" } else { "1" })
+            .collect::<Vec<&str>>();
+        txt.push_str(&row.join(" "));
+        txt.push('\n');
+    });
+    txt
+}
+
+/// Plain (P2) PGM. Unlike the PBM this keeps pixels that are transparent all
+/// the way down distinct from black ones.
+pub fn to_pgm(pixels: &Grid<Option<bool>>) -> String {
+    const PIX_TRANSPARENT: u8 = 128;
+    const PIX_BLACK: u8 = 0;
+    const PIX_WHITE: u8 = 255;
+
+    let mut txt = format!("P2\n{} {}\n{}\n", pixels.width(), pixels.height(), PIX_WHITE);
+    pixels.rows().for_each(|row| {
+        let row = row.iter()
+            .map(|pix| match pix {
+                None => PIX_TRANSPARENT,
+                Some(false) => PIX_BLACK,
+                Some(true) => PIX_WHITE,
+            })
+            .map(|val| val.to_string())
+            .collect::<Vec<String>>();
+        txt.push_str(&row.join(" "));
+        txt.push('\n');
+    });
+    txt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let image = Image::decode("123456789012\n", 3, 2).unwrap();
+        assert_eq!(image.layers.len(), 2);
+        assert_eq!(image.layers[0].pixels.row(1), &[4, 5, 6]);
+        assert_eq!(image.layers[1].count(0), 1);
+        assert_eq!(image.encode(), "123456789012");
+
+        assert_eq!(Image::decode("12345", 3, 2), Err(SifError::BadLength { len: 5, layer_len: 6 }));
+        assert_eq!(Image::decode("12x456", 3, 2), Err(SifError::BadDigit { pos: 2, c: 'x' }));
+        assert_eq!(Image::decode("", 3, 2), Err(SifError::NoLayers));
+        assert_eq!(Image::decode("123", 3, 0), Err(SifError::EmptySize));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let image = Image::decode("0222112222120000", 2, 2).unwrap();
+        assert_eq!(image.flatten().unwrap().cells(), &[false, true, true, false]);
+
+        let image = Image::decode("2221", 2, 2).unwrap();
+        assert_eq!(image.composite().unwrap().cells(), &[None, None, None, Some(true)]);
+        assert_eq!(Image::decode("0123", 2, 2).unwrap().flatten(),
+            Err(SifError::BadColor { layer: 0, x: 1, y: 1, digit: 3 }));
+    }
+
+    #[test]
+    fn test_from_bitmaps() {
+        let front = Grid::from_cells(2, vec![None, Some(true), None, None]).unwrap();
+        let back = Grid::from_cells(2, vec![Some(true), Some(false), Some(false), None]).unwrap();
+        let image = Image::from_bitmaps(&[front.clone(), back]).unwrap();
+        assert_eq!(image.encode(), "21221002");
+        assert_eq!(Image::decode(&image.encode(), 2, 2), Ok(image.clone()));
+        assert_eq!(image.composite().unwrap().cells(),
+            &[Some(true), Some(true), Some(false), None]);
+
+        let small = Grid::from_cells(1, vec![None]).unwrap();
+        assert_eq!(Image::from_bitmaps(&[front, small]),
+            Err(SifError::LayerSize { layer: 1, width: 1, height: 1 }));
+        assert_eq!(Image::from_bitmaps(&[]), Err(SifError::NoLayers));
+    }
+
+    #[test]
+    fn test_export() {
+        let image = Image::decode("2102", 2, 2).unwrap();
+        assert_eq!(to_pbm(&image.flatten().unwrap()), "P1\n2 2\n1 0\n1 1\n");
+        assert_eq!(to_pgm(&image.composite().unwrap()), "P2\n2 2\n255\n128 255\n0 128\n");
+    }
+}
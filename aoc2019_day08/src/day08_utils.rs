@@ -1,42 +1,10 @@
-use std::convert::TryInto;
+use crate::day08_sif::*;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Layer {
-    pub digit_counts: [u32; 10],
-}
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Image {
-    pub width: u32,
-    pub height: u32,
-    pub layers: Vec<Layer>,
-}
-
-pub fn decode_image(data_str: &str, width: u32, height: u32) -> Image {
-    let data = data_str.as_bytes();
-    let pix_in_image = width * height;
-    let data_len: u32 = data.len().try_into().unwrap();
-    assert_eq!(data_len % pix_in_image, 0);
-    let num_layers = data_len / pix_in_image;
-
-    let mut img = Image {
-        width: width,
-        height: height,
-        layers: vec![],
-    };
-
-    img.layers = (0..num_layers).map(|layer_num| {
-        let start = layer_num * pix_in_image;
-        let end = start + pix_in_image;
-        let mut layer = Layer { digit_counts: [0; 10]};
-        for byte in &data[start as usize..end as usize] {
-            layer.digit_counts[(byte - b'0') as usize] += 1;
-        }
-        layer
-    })
-    .collect();
-
-    img
+/// The number of 1s times the number of 2s on the layer with the fewest 0s.
+pub fn get_checksum(img: &Image) -> Option<usize> {
+    img.layers.iter()
+        .min_by_key(|layer| layer.count(0))
+        .map(|layer| layer.count(1) * layer.count(2))
 }
 
 #[cfg(test)]
@@ -44,16 +12,8 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_decode_image() {
-        let input = "123456789012";
-        let result = decode_image(input, 3, 2);
-        assert_eq!(result, Image {
-            width: 3,
-            height: 2,
-            layers: vec![
-                Layer { digit_counts: [0, 1, 1, 1, 1, 1, 1, 0, 0, 0] },
-                Layer { digit_counts: [1, 1, 1, 0, 0, 0, 0, 1, 1, 1] },
-            ],
-        });
+    fn test_get_checksum() {
+        assert_eq!(get_checksum(&Image::decode("123456789012", 3, 2).unwrap()), Some(1));
+        assert_eq!(get_checksum(&Image::decode("0222112222120000", 2, 2).unwrap()), Some(4));
     }
 }
@@ -1,40 +1,39 @@
-pub mod day08_utils;
+use aoc2019_utils::{ocr, parse_args, write_file};
+use aoc2019_utils::solution::Answer;
 
-use aoc2019_utils;
-
-use day08_utils::*;
+use aoc2019_day08::day08_sif::*;
 
 const WIDTH: usize = 25;
 const HEIGHT: usize = 6;
 
 fn main() {
-    let input = aoc2019_utils::get_input("inputs/day08.txt");
-    let img = decode_image(&input, 25, 6);
-    let input = input.as_bytes();
-
-    let mut canvas = [[b' '; HEIGHT]; WIDTH];
-
-    let pix_in_image = WIDTH * HEIGHT;
-    (0..img.layers.len()).rev().for_each(|layer_num| {
-        let mut idx = layer_num * pix_in_image;
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                canvas[x][y] = match input[idx] {
-                    b'0' => b' ',
-                    b'1' => b'#',
-                    b'2' => canvas[x][y],
-                    _ => panic!("unexpected byte: {}", input[idx] as char),
-                };
-
-                idx += 1;
-            }
+    let mut pbm_file = None;
+    let mut pgm_file = None;
+    parse_args("--pbm <file>, --pgm <file>", |arg, next_arg| {
+        match arg {
+            "--pbm" => pbm_file = Some(next_arg()),
+            "--pgm" => pgm_file = Some(next_arg()),
+            _ => return false,
         }
+        true
     });
 
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            print!("{}", canvas[x][y] as char);
-        }
-        println!("");
+    let input = aoc2019_utils::get_input("inputs/day08.txt");
+    let image = Image::decode(&input, WIDTH, HEIGHT)
+        .unwrap_or_else(|err| panic!("bad image: {}", err));
+    let composite = image.composite().unwrap_or_else(|err| panic!("bad image: {}", err));
+    let pixels = composite.map(|pix| pix.unwrap_or(false));
+    print!("{}", Answer::render_pixels(&pixels));
+
+    match ocr::read_text(&pixels) {
+        Ok(letters) => println!("message: {}", letters),
+        Err(err) => println!("couldn't read the message: {}", err),
+    }
+
+    if let Some(filename) = &pbm_file {
+        write_file(filename, &to_pbm(&pixels));
+    }
+    if let Some(filename) = &pgm_file {
+        write_file(filename, &to_pgm(&composite));
     }
 }
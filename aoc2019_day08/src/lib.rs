@@ -0,0 +1,46 @@
+pub mod day08_sif;
+pub mod day08_utils;
+
+use aoc2019_utils::ocr;
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day08_sif::*;
+use day08_utils::*;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Result<Image, SifError>;
+
+    fn get_day(&self) -> u32 {
+        8
+    }
+
+    fn parse(&self, input: &str) -> Result<Image, SifError> {
+        Image::decode(input, WIDTH, HEIGHT)
+    }
+
+    fn solve_a(&self, image: &Result<Image, SifError>) -> Answer {
+        match image {
+            Ok(image) => get_checksum(image).into(),
+            Err(err) => err.to_string().into(),
+        }
+    }
+
+    fn solve_b(&self, image: &Result<Image, SifError>) -> Answer {
+        let pixels = match image {
+            Ok(image) => image.flatten(),
+            Err(err) => Err(err.clone()),
+        };
+        match pixels {
+            Ok(pixels) => {
+                let text = ocr::read_text(&pixels).ok();
+                Answer::image(pixels, text)
+            },
+            Err(err) => err.to_string().into(),
+        }
+    }
+}
@@ -38,9 +38,9 @@ enum ParamMode {
 impl Cpu {
     const MEM_SIZE: usize = 1024 * 1024;
 
-    pub fn new(prog: &Vec<i64>) -> Cpu {
+    pub fn new(prog: &[i64]) -> Cpu {
         let mut mem = vec![0; Self::MEM_SIZE];
-        mem[..prog.len()].copy_from_slice(&prog);
+        mem[..prog.len()].copy_from_slice(prog);
         Cpu {
             input: vec![],
             mem: mem,
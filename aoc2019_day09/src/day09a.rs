@@ -1,4 +1,4 @@
-pub mod day09_cpu;
+use aoc2019_day09::day09_cpu;
 
 use aoc2019_utils;
 
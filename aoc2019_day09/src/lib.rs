@@ -0,0 +1,35 @@
+pub mod day09_cpu;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day09_cpu::*;
+
+fn run_boost(prog: &[i64], mode: i64) -> Answer {
+    let mut cpu = Cpu::new(prog);
+    cpu.set_print_output(false);
+    cpu.add_input(mode);
+    cpu.exec_prog();
+    cpu.pop_output().into()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        9
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        run_boost(prog, 1)
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        run_boost(prog, 2)
+    }
+}
@@ -5,25 +5,11 @@ use aoc2019_utils::*;
 
 type Coord = point_2d::Point2d<i32>;
 
-type Grid = Vec<Vec<char>>;
+pub type Grid = grid::Grid<char>;
 
 pub fn parse_input(input: &str) -> Grid {
-    let height = input.lines().count();
-    assert!(height > 0);
-    let width = input.lines().next().unwrap().len();
-    assert!(width > 0);
-
-    let mut grid: Grid = (0..width).map(|_| {
-        vec![' '; height]
-    })
-    .collect();
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid[x][y] = c;
-        }
-    }
-
+    let grid = Grid::parse(input, Some).expect("the asteroid map isn't rectangular");
+    assert!(!grid.is_empty());
     grid
 }
 
@@ -37,14 +23,8 @@ fn gen_radial_search(start: Coord, width: usize, height: usize) -> Vec<Coord> {
     });
 
     coords.sort_by(|a, b| {
-        let dist_a = std::cmp::max(
-            (a.x - start.x).abs(),
-            (a.y - start.y).abs(),
-        );
-        let dist_b = std::cmp::max(
-            (b.x - start.x).abs(),
-            (b.y - start.y).abs(),
-        );
+        let dist_a = a.chebyshev(start);
+        let dist_b = b.chebyshev(start);
 
         if dist_a < dist_b {
             return Ordering::Less;
@@ -70,13 +50,13 @@ fn gen_radial_search(start: Coord, width: usize, height: usize) -> Vec<Coord> {
 fn num_visible_asteroids(start: Coord, grid: &Grid) -> u32 {
     let mut grid = grid.clone();
 
-    let width = grid.len();
-    let height = grid[0].len();
+    let width = grid.width();
+    let height = grid.height();
     let mut num_visible = 0;
 
     let coords = gen_radial_search(start, width, height);
     for coord in coords[1..].iter() {
-        if grid[coord.x as usize][coord.y as usize] != '#' {
+        if grid[*coord] != '#' {
             continue;
         }
 
@@ -89,16 +69,10 @@ fn num_visible_asteroids(start: Coord, grid: &Grid) -> u32 {
         loop {
             new_coord += offset;
 
-            if new_coord.x < 0
-                || new_coord.y < 0
-                || new_coord.x >= width as i32
-                || new_coord.y >= height as i32
-            {
-                break;
-            }
-
-            if grid[new_coord.x as usize][new_coord.y as usize] == '#' {
-                grid[new_coord.x as usize][new_coord.y as usize] = 'X';
+            match grid.get_mut(new_coord) {
+                None => break,
+                Some(c) if *c == '#' => *c = 'X',
+                Some(_) => {},
             }
         }
     }
@@ -107,19 +81,19 @@ fn num_visible_asteroids(start: Coord, grid: &Grid) -> u32 {
 }
 
 pub fn max_visible_asteroids_loc(grid: &Grid) -> Option<(u32, Coord)> {
-    let width = grid.len();
-    let height = grid[0].len();
+    let width = grid.width();
+    let height = grid.height();
 
     let mut max_visibles = None;
     let mut result = None;
 
     (0..width).for_each(|x| {
         (0..height).for_each(|y| {
-            if grid[x][y] != '#' {
+            let coord = Coord { x: x as i32, y: y as i32 };
+            if grid[coord] != '#' {
                 return;
             }
 
-            let coord = Coord { x: x as i32, y: y as i32 };
             let num_visible = num_visible_asteroids(coord, &grid);
             if !max_visibles.is_some()
                 || (num_visible > max_visibles.unwrap())
@@ -136,12 +110,12 @@ pub fn max_visible_asteroids_loc(grid: &Grid) -> Option<(u32, Coord)> {
 fn collect_by_angles(grid: &Grid, center: Coord) -> Vec<Vec<Coord>> {
     let mut angle_buckets = HashMap::new();
 
-    let width = grid.len();
-    let height = grid[0].len();
+    let width = grid.width();
+    let height = grid.height();
 
     (0..width).for_each(|x| {
         (0..height).for_each(|y| {
-            if grid[x][y] != '#' {
+            if grid[Coord { x: x as i32, y: y as i32 }] != '#' {
                 return;
             }
 
@@ -181,15 +155,7 @@ fn collect_by_angles(grid: &Grid, center: Coord) -> Vec<Vec<Coord>> {
 
     for coords in &mut angle_buckets {
         coords.sort_by(|a, b| {
-            let dist_a = std::cmp::max(
-                (a.x - center.x).abs(),
-                (a.y - center.y).abs(),
-            );
-            let dist_b = std::cmp::max(
-                (b.x - center.x).abs(),
-                (b.y - center.y).abs(),
-            );
-            dist_a.cmp(&dist_b)
+            a.chebyshev(center).cmp(&b.chebyshev(center))
         });
     }
 
@@ -236,15 +202,13 @@ mod tests {
             "....#\n",
             "...##\n",
         );
-        let target = vec![
-            vec!['.', '.', '#', '.', '.'],
-            vec!['#', '.', '#', '.', '.'],
-            vec!['.', '.', '#', '.', '.'],
-            vec!['.', '.', '#', '.', '#'],
-            vec!['#', '.', '#', '#', '#'],
-        ];
         let grid = parse_input(input);
-        assert_eq!(grid, target);
+        assert_eq!((grid.width(), grid.height()), (5, 5));
+        assert_eq!(grid.column(0).collect::<String>(), "..#..");
+        assert_eq!(grid.column(1).collect::<String>(), "#.#..");
+        assert_eq!(grid.column(2).collect::<String>(), "..#..");
+        assert_eq!(grid.column(3).collect::<String>(), "..#.#");
+        assert_eq!(grid.column(4).collect::<String>(), "#.###");
     }
 
     #[test]
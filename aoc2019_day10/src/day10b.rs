@@ -1,4 +1,4 @@
-pub mod day10_utils;
+use aoc2019_day10::day10_utils;
 
 use aoc2019_utils;
 use day10_utils::*;
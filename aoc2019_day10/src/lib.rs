@@ -0,0 +1,32 @@
+pub mod day10_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day10_utils::*;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Grid;
+
+    fn get_day(&self) -> u32 {
+        10
+    }
+
+    fn parse(&self, input: &str) -> Grid {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, grid: &Grid) -> Answer {
+        max_visible_asteroids_loc(grid)
+            .map(|(num_visible, _)| num_visible)
+            .into()
+    }
+
+    fn solve_b(&self, grid: &Grid) -> Answer {
+        max_visible_asteroids_loc(grid)
+            .and_then(|(_, coord)| get_nth_shot(grid, coord, 200))
+            .map(|shot_coord| shot_coord.x * 100 + shot_coord.y)
+            .into()
+    }
+}
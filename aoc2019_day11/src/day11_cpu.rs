@@ -1,6 +1,8 @@
 use std::convert::TryInto;
 use std::str::FromStr;
 
+use aoc2019_utils::grid_robot::IntcodeMachine;
+
 const ADD_OP: i64 = 1;
 const MUL_OP: i64 = 2;
 const READ_OP: i64 = 3;
@@ -272,6 +274,24 @@ impl Cpu {
     }
 }
 
+impl IntcodeMachine for Cpu {
+    fn add_input(&mut self, input: i64) {
+        Cpu::add_input(self, input);
+    }
+
+    fn run(&mut self) {
+        self.exec_prog();
+    }
+
+    fn pop_output(&mut self) -> Option<i64> {
+        Cpu::pop_output(self)
+    }
+
+    fn is_done(&self) -> bool {
+        self.state == CpuState::Done
+    }
+}
+
 pub fn parse_prog(instr_txt: &str) -> Vec<i64> {
     instr_txt
         .split(",")
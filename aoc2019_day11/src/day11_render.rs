@@ -0,0 +1,311 @@
+use std::io::Write;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use aoc2019_utils::{parse_args, write_file};
+
+use crate::day11_utils::*;
+
+const SVG_TILE_SIZE: i32 = 10;
+const DEFAULT_FRAME_MS: u64 = 20;
+
+/// Export and animation options shared by both Day 11 binaries.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RenderOptions {
+    pub pbm_file: Option<String>,
+    pub pgm_file: Option<String>,
+    pub svg_file: Option<String>,
+    pub svg_path: bool,
+    pub animate: bool,
+    pub frame_delay: Option<Duration>,
+}
+
+impl RenderOptions {
+    pub fn from_args() -> Self {
+        let mut options = Self::default();
+        let usage = "--pbm <file>, --pgm <file>, --svg <file>, --svg-path, --animate, \
+            --frame-ms <ms>";
+        parse_args(usage, |arg, next_arg| {
+            match arg {
+                "--pbm" => options.pbm_file = Some(next_arg()),
+                "--pgm" => options.pgm_file = Some(next_arg()),
+                "--svg" => options.svg_file = Some(next_arg()),
+                "--svg-path" => options.svg_path = true,
+                "--animate" => options.animate = true,
+                "--frame-ms" => {
+                    let ms = u64::from_str(&next_arg()).expect("bad --frame-ms value");
+                    options.frame_delay = Some(Duration::from_millis(ms));
+                },
+                _ => return false,
+            }
+            true
+        });
+        options
+    }
+
+    pub fn needs_history(&self) -> bool {
+        self.animate || self.svg_path
+    }
+}
+
+pub fn do_renders(
+    options: &RenderOptions,
+    grid: &Grid,
+    history: &[PathStep],
+    start_on_white: bool,
+) {
+    if options.animate {
+        let frame_delay = options.frame_delay
+            .unwrap_or_else(|| Duration::from_millis(DEFAULT_FRAME_MS));
+        animate(history, start_on_white, frame_delay);
+    }
+    if let Some(filename) = &options.pbm_file {
+        write_file(filename, &to_pbm(grid));
+    }
+    if let Some(filename) = &options.pgm_file {
+        write_file(filename, &to_pgm(grid));
+    }
+    if let Some(filename) = &options.svg_file {
+        let history = if options.svg_path { history } else { &[] };
+        write_file(filename, &to_svg(grid, history));
+    }
+}
+
+fn get_export_bounds(grid: &Grid, history: &[PathStep]) -> Option<(Coord, Coord)> {
+    get_bounds(grid.iter().map(|(coord, _)| coord).chain(history.iter().map(|step| step.pos)))
+}
+
+fn get_heading_char(dir: Dir) -> char {
+    match dir {
+        Dir::Up => '^',
+        Dir::Down => 'v',
+        Dir::Left => '<',
+        Dir::Right => '>',
+    }
+}
+
+/// Plain (P1) PBM. Ink is black, so white hull tiles are written as 0.
+pub fn to_pbm(grid: &Grid) -> String {
+    let (min_coord, max_coord) = match grid.bounds() {
+        Some(bounds) => bounds,
+        None => return "P1\n0 0\n".to_string(),
+    };
+    let width = max_coord.x - min_coord.x + 1;
+    let height = max_coord.y - min_coord.y + 1;
+
+    let mut txt = format!("P1\n{} {}\n", width, height);
+    (min_coord.y..=max_coord.y).for_each(|y| {
+        let row = (min_coord.x..=max_coord.x)
+            .map(|x| match get_color_at(grid, Coord { x, y }) {
+                TileColor::White => "0",
+                TileColor::Black => "1",
+            })
+            .collect::<Vec<&str>>();
+        txt.push_str(&row.join(" "));
+        txt.push('\n');
+    });
+    txt
+}
+
+/// Plain (P2) PGM. Unlike the PBM this keeps tiles the robot painted black
+/// distinct from tiles it never touched, which shows how much of the hull it
+/// covered.
+pub fn to_pgm(grid: &Grid) -> String {
+    const UNPAINTED: u8 = 0;
+    const PAINTED_BLACK: u8 = 64;
+    const PAINTED_WHITE: u8 = 255;
+
+    let (min_coord, max_coord) = match grid.bounds() {
+        Some(bounds) => bounds,
+        None => return format!("P2\n0 0\n{}\n", PAINTED_WHITE),
+    };
+    let width = max_coord.x - min_coord.x + 1;
+    let height = max_coord.y - min_coord.y + 1;
+
+    let mut txt = format!("P2\n{} {}\n{}\n", width, height, PAINTED_WHITE);
+    (min_coord.y..=max_coord.y).for_each(|y| {
+        let row = (min_coord.x..=max_coord.x)
+            .map(|x| Coord { x, y })
+            .map(|coord| match (grid.contains(coord), *grid.get(coord)) {
+                (false, _) => UNPAINTED,
+                (true, TileColor::Black) => PAINTED_BLACK,
+                (true, TileColor::White) => PAINTED_WHITE,
+            })
+            .map(|val| val.to_string())
+            .collect::<Vec<String>>();
+        txt.push_str(&row.join(" "));
+        txt.push('\n');
+    });
+    txt
+}
+
+/// SVG of the hull, optionally with the robot's path drawn over it and an
+/// arrow showing its final heading.
+pub fn to_svg(grid: &Grid, history: &[PathStep]) -> String {
+    let (min_coord, max_coord) = match get_export_bounds(grid, history) {
+        Some(bounds) => bounds,
+        None => {
+            return "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 0 0\" \
+                width=\"0\" height=\"0\">\n</svg>\n".to_string();
+        },
+    };
+    let width = (max_coord.x - min_coord.x + 1) * SVG_TILE_SIZE;
+    let height = (max_coord.y - min_coord.y + 1) * SVG_TILE_SIZE;
+    let to_svg_coord = |coord: Coord| {
+        ((coord.x - min_coord.x) * SVG_TILE_SIZE,
+            (coord.y - min_coord.y) * SVG_TILE_SIZE)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" \
+            width=\"{}\" height=\"{}\">\n",
+        width, height, width, height);
+    svg.push_str(&format!(
+        "  <rect width=\"{}\" height=\"{}\" fill=\"black\"/>\n", width, height));
+
+    let mut white_tiles = grid.iter()
+        .filter(|(_, color)| **color == TileColor::White)
+        .map(|(coord, _)| coord)
+        .collect::<Vec<Coord>>();
+    white_tiles.sort_by_key(|coord| (coord.y, coord.x));
+    white_tiles.iter().for_each(|coord| {
+        let (x, y) = to_svg_coord(*coord);
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+            x, y, SVG_TILE_SIZE, SVG_TILE_SIZE));
+    });
+
+    if let Some(last_step) = history.last() {
+        let half = SVG_TILE_SIZE / 2;
+        let points = std::iter::once(history[0].painted)
+            .chain(history.iter().map(|step| step.pos))
+            .map(|coord| {
+                let (x, y) = to_svg_coord(coord);
+                format!("{},{}", x + half, y + half)
+            })
+            .collect::<Vec<String>>();
+        svg.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"red\" \
+                stroke-width=\"1\" stroke-opacity=\"0.6\"/>\n",
+            points.join(" ")));
+
+        let (x, y) = to_svg_coord(last_step.pos);
+        let rotation = match last_step.dir {
+            Dir::Up => 0,
+            Dir::Right => 90,
+            Dir::Down => 180,
+            Dir::Left => 270,
+        };
+        svg.push_str(&format!(
+            "  <polygon points=\"{},{} {},{} {},{}\" fill=\"red\" \
+                transform=\"rotate({} {} {})\"/>\n",
+            x + half, y, x + SVG_TILE_SIZE, y + SVG_TILE_SIZE,
+            x, y + SVG_TILE_SIZE, rotation, x + half, y + half));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn render_frame(grid: &Grid, robot: Option<&PathStep>, bounds: (Coord, Coord))
+-> String {
+    let (min_coord, max_coord) = bounds;
+    let mut frame = String::new();
+    (min_coord.y..=max_coord.y).for_each(|y| {
+        (min_coord.x..=max_coord.x).for_each(|x| {
+            let coord = Coord { x, y };
+            let c = match robot {
+                Some(step) if step.pos == coord => get_heading_char(step.dir),
+                _ => match get_color_at(grid, coord) {
+                    TileColor::White => '#',
+                    TileColor::Black => ' ',
+                },
+            };
+            frame.push(c);
+        });
+        frame.push('\n');
+    });
+    frame
+}
+
+/// Replays a recorded run in the terminal, repainting the hull one step at a
+/// time with the robot drawn as an arrow showing its heading.
+pub fn animate(history: &[PathStep], start_on_white: bool, frame_delay: Duration) {
+    let mut grid = get_start_grid(start_on_white);
+    let bounds = match get_bounds(history.iter()
+        .flat_map(|step| vec![step.painted, step.pos].into_iter()))
+    {
+        Some(bounds) => bounds,
+        None => return,
+    };
+
+    print!("\x1b[2J\x1b[?25l");
+    history.iter().enumerate().for_each(|(i, step)| {
+        grid.set(step.painted, step.color);
+        print!("\x1b[H{}step {}/{}\x1b[J",
+            render_frame(&grid, Some(step), bounds), i + 1, history.len());
+        std::io::stdout().flush().unwrap();
+        thread::sleep(frame_delay);
+    });
+    println!("\x1b[?25h");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_grid() -> Grid {
+        let mut grid = Grid::default();
+        grid.set(Coord { x: 0, y: 0 }, TileColor::White);
+        grid.set(Coord { x: 1, y: 0 }, TileColor::Black);
+        grid.set(Coord { x: 2, y: 1 }, TileColor::White);
+        grid
+    }
+
+    #[test]
+    fn test_to_pbm() {
+        assert_eq!(to_pbm(&make_grid()), "P1\n3 2\n0 1 1\n1 1 0\n");
+    }
+
+    #[test]
+    fn test_to_pgm() {
+        assert_eq!(to_pgm(&make_grid()), "P2\n3 2\n255\n255 64 0\n0 0 255\n");
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        assert_eq!(to_pbm(&Grid::default()), "P1\n0 0\n");
+        assert_eq!(to_pgm(&Grid::default()), "P2\n0 0\n255\n");
+        assert!(to_svg(&Grid::default(), &[]).contains("viewBox=\"0 0 0 0\""));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let history = vec![
+            PathStep {
+                painted: Coord { x: 0, y: 0 },
+                color: TileColor::White,
+                pos: Coord { x: -1, y: 0 },
+                dir: Dir::Left,
+            },
+        ];
+        let svg = to_svg(&make_grid(), &history);
+        assert!(svg.contains("viewBox=\"0 0 40 20\""));
+        assert_eq!(svg.matches("fill=\"white\"").count(), 2);
+        assert!(svg.contains("points=\"15,5 5,5\""));
+        assert!(svg.contains("rotate(270 5 5)"));
+    }
+
+    #[test]
+    fn test_render_frame() {
+        let robot = PathStep {
+            painted: Coord { x: 1, y: 0 },
+            color: TileColor::Black,
+            pos: Coord { x: 1, y: 1 },
+            dir: Dir::Down,
+        };
+        let bounds = (Coord { x: 0, y: 0 }, Coord { x: 2, y: 1 });
+        assert_eq!(render_frame(&make_grid(), Some(&robot), bounds), "#  \n v#\n");
+    }
+}
@@ -1,49 +1,12 @@
-use std::collections::HashMap;
-
-use aoc2019_utils::*;
+use aoc2019_utils::grid;
+use aoc2019_utils::grid_robot::*;
 use crate::day11_cpu::*;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum TurnDir { Left, Right }
-
-impl TurnDir {
-    fn from_num(num: i64) -> Self {
-        match num {
-            0 => Self::Left,
-            1 => Self::Right,
-            _ => panic!("invalid TurnDir num: {}", num),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Dir { Up, Down, Left, Right }
+pub use aoc2019_utils::grid_robot::Dir;
 
-impl Dir {
-    fn turn(self, turn_dir: TurnDir) -> Self {
-        match self {
-            Self::Up => match turn_dir {
-                TurnDir::Left => Self::Left,
-                TurnDir::Right => Self::Right,
-            },
-            Self::Down => match turn_dir {
-                TurnDir::Left => Self::Right,
-                TurnDir::Right => Self::Left,
-            },
-            Self::Left => match turn_dir {
-                TurnDir::Left => Self::Down,
-                TurnDir::Right => Self::Up,
-            },
-            Self::Right => match turn_dir {
-                TurnDir::Left => Self::Up,
-                TurnDir::Right => Self::Down,
-            },
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum TileColor {
+    #[default]
     Black,
     White,
 }
@@ -51,16 +14,20 @@ pub enum TileColor {
 impl TileColor {
     const BLACK: i64 = 0;
     const WHITE: i64 = 1;
+}
 
-    fn from_num(num: i64) -> Self {
+impl FromIntcode for TileColor {
+    fn from_intcode(num: i64) -> Self {
         match num {
             Self::BLACK => Self::Black,
             Self::WHITE => Self::White,
             _ => panic!("invalid TileColor num: {}", num),
         }
     }
+}
 
-    fn to_num(self) -> i64 {
+impl ToIntcode for TileColor {
+    fn to_intcode(self) -> i64 {
         match self {
             Self::Black => Self:: BLACK,
             Self::White => Self:: WHITE,
@@ -68,98 +35,105 @@ impl TileColor {
     }
 }
 
-pub type Coord = point_2d::Point2d<i32>;
+pub type Coord = RobotCoord;
 
-pub type Grid = HashMap<Coord, TileColor>;
+pub type Grid = grid::SparseGrid<TileColor>;
 
-pub struct Robot {
-    pos: Coord,
-    dir: Dir,
-    cpu: Cpu,
+/// One step of the robot's run: the tile it painted and the color it used,
+/// followed by where it ended up and which way it faces afterwards.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PathStep {
+    pub painted: Coord,
+    pub color: TileColor,
+    pub pos: Coord,
+    pub dir: Dir,
 }
 
-impl Robot {
-    pub fn new(pos: Coord, prog: &Vec<i64>) -> Self {
-        let mut cpu = Cpu::new(prog);
-        cpu.set_print_output(false);
-        Robot {
-            pos: pos,
-            dir: Dir::Up,
-            cpu: cpu,
-        }
-    }
+pub type Robot = GridRobot<Cpu, PaintProtocol<Grid>>;
 
-    pub fn get_pos(&self) -> Coord {
-        self.pos
-    }
+pub fn new_robot(pos: Coord, prog: &[i64]) -> Robot {
+    let mut cpu = Cpu::new(&prog.to_vec());
+    cpu.set_print_output(false);
+    GridRobot::new(cpu, PaintProtocol::default(), Pose { pos, dir: Dir::Up })
+}
 
-    pub fn move_fwd(&mut self) {
-        self.pos = match self.dir {
-            Dir::Up => Coord { x: self.pos.x, y: self.pos.y - 1 },
-            Dir::Down =>  Coord { x: self.pos.x, y: self.pos.y + 1 },
-            Dir::Left => Coord { x: self.pos.x - 1, y: self.pos.y },
-            Dir::Right => Coord { x: self.pos.x + 1, y: self.pos.y },
-        }
+pub fn do_step(grid: &mut Grid, robot: &mut Robot) -> Option<PathStep> {
+    match robot.step(grid, ()) {
+        Ok(painted) => Some(PathStep {
+            painted: painted.pos,
+            color: painted.cell,
+            pos: robot.get_pos(),
+            dir: robot.get_dir(),
+        }),
+        Err(StepError::Halted) => None,
+        Err(err) => panic!("bad robot program: {}", err),
     }
+}
 
-    pub fn step_with_input(&mut self, color: TileColor) -> (bool, TileColor) {
-        self.cpu.add_input(color.to_num());
-        self.cpu.exec_prog();
-
-        let color = TileColor::from_num(self.cpu.pop_output().unwrap());
-        let turn_dir = TurnDir::from_num(self.cpu.pop_output().unwrap());
+pub fn get_start_grid(start_on_white: bool) -> Grid {
+    let mut grid = Grid::default();
+    if start_on_white {
+        grid.set(Coord { x: 0, y: 0 }, TileColor::White);
+    }
+    grid
+}
 
-        self.dir = self.dir.turn(turn_dir);
-        self.move_fwd();
+pub fn run_robot_sim(prog: &[i64], start_on_white: bool) -> Grid {
+    run_robot_sim_with_history(prog, start_on_white).0
+}
 
-        (self.cpu.get_state() != CpuState::Done, color)
+pub fn run_robot_sim_with_history(prog: &[i64], start_on_white: bool)
+-> (Grid, Vec<PathStep>) {
+    let mut grid = get_start_grid(start_on_white);
+    let mut robot = new_robot(Coord { x: 0, y: 0 }, prog);
+    let mut history = vec![];
+    while let Some(step) = do_step(&mut grid, &mut robot) {
+        history.push(step);
+        if robot.is_done() {
+            break;
+        }
     }
+    (grid, history)
 }
 
-pub fn do_step(grid: &mut Grid, robot: &mut Robot) -> bool {
-    let start_pos = robot.get_pos();
-    let start_color = match grid.get(&start_pos) {
-        None => TileColor::Black,
-        Some(color) => *color,
-    };
-    let (cont, end_color) = robot.step_with_input(start_color);
-    grid.insert(start_pos, end_color);
-    cont
+/// The corners of the smallest box around the coords, or `None` if there
+/// aren't any, as when the robot halts before painting.
+pub fn get_bounds<I>(coords: I) -> Option<(Coord, Coord)>
+where
+    I: Iterator<Item = Coord>,
+{
+    coords.fold(None, |bounds, coord| {
+        let (min_coord, max_coord) = bounds.unwrap_or((coord, coord));
+        let min_coord = Coord { x: min_coord.x.min(coord.x), y: min_coord.y.min(coord.y) };
+        let max_coord = Coord { x: max_coord.x.max(coord.x), y: max_coord.y.max(coord.y) };
+        Some((min_coord, max_coord))
+    })
 }
 
-pub fn run_robot_sim(prog: &Vec<i64>, start_on_white: bool) -> Grid {
-    let mut grid = HashMap::new();
-    if start_on_white {
-        grid.insert(Coord { x: 0, y: 0 }, TileColor::White);
-    }
-    let mut robot = Robot::new(Coord { x: 0, y: 0 }, &prog);
-    while do_step(&mut grid, &mut robot) {}
-    grid
+pub fn get_color_at(grid: &Grid, coord: Coord) -> TileColor {
+    *grid.get(coord)
+}
+
+/// The white tiles as an image, cropped to the painted area.
+pub fn to_image(grid: &Grid) -> grid::Grid<bool> {
+    let mut image = grid::SparseGrid::new(false);
+    grid.iter()
+        .filter(|(_, color)| **color == TileColor::White)
+        .for_each(|(coord, _)| { image.set(coord, true); });
+    image.to_grid().0
 }
 
 pub fn print_grid(grid: &Grid) {
-    let min_coord = Coord { x: i32::max_value(), y: i32::max_value() };
-    let max_coord = Coord { x: i32::min_value(), y: i32::min_value() };
-    let (min_coord, max_coord) = grid.keys()
-        .fold((min_coord, max_coord), |(min_coord, max_coord), coord| {
-            let min_x = std::cmp::min(min_coord.x, coord.x);
-            let min_y = std::cmp::min(min_coord.y, coord.y);
-            let max_x = std::cmp::max(max_coord.x, coord.x);
-            let max_y = std::cmp::max(max_coord.y, coord.y);
-            let min_coord = Coord { x: min_x, y: min_y };
-            let max_coord = Coord { x: max_x, y: max_y };
-            (min_coord, max_coord)
-        });
+    let (min_coord, max_coord) = match grid.bounds() {
+        Some(bounds) => bounds,
+        None => return,
+    };
 
     println!("{:?} -> {:?}", min_coord, max_coord);
     (min_coord.y..=max_coord.y).for_each(|y| {
         (min_coord.x..=max_coord.x).for_each(|x| {
-            if let Some(color) = grid.get(&Coord { x: x, y: y }) {
-                if *color == TileColor::White {
-                    print!("#");
-                } else {
-                    print!(" ");
-                }
+            if *grid.get(Coord { x, y }) == TileColor::White {
+                print!("#");
             } else {
                 print!(" ");
             }
@@ -173,38 +147,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_turn_dir_from_num() {
-        assert_eq!(TurnDir::from_num(0), TurnDir::Left);
-        assert_eq!(TurnDir::from_num(1), TurnDir::Right);
-    }
-
-    #[test]
-    fn test_dir_turn() {
-        assert_eq!(Dir::Up.turn(TurnDir::Left), Dir::Left);
-        assert_eq!(Dir::Up.turn(TurnDir::Right), Dir::Right);
-        assert_eq!(Dir::Down.turn(TurnDir::Left), Dir::Right);
-        assert_eq!(Dir::Down.turn(TurnDir::Right), Dir::Left);
-        assert_eq!(Dir::Left.turn(TurnDir::Left), Dir::Down);
-        assert_eq!(Dir::Left.turn(TurnDir::Right), Dir::Up);
-        assert_eq!(Dir::Right.turn(TurnDir::Left), Dir::Up);
-        assert_eq!(Dir::Right.turn(TurnDir::Right), Dir::Down);
-    }
-
-    #[test]
-    fn test_tile_color_from_num() {
-        assert_eq!(TileColor::from_num(0), TileColor::Black);
-        assert_eq!(TileColor::from_num(1), TileColor::White);
+    fn test_tile_color_from_intcode() {
+        assert_eq!(TileColor::from_intcode(0), TileColor::Black);
+        assert_eq!(TileColor::from_intcode(1), TileColor::White);
     }
 
     #[test]
-    fn test_tile_color_to_num() {
-        assert_eq!(TileColor::Black.to_num(), 0);
-        assert_eq!(TileColor::White.to_num(), 1);
+    fn test_tile_color_to_intcode() {
+        assert_eq!(TileColor::Black.to_intcode(), 0);
+        assert_eq!(TileColor::White.to_intcode(), 1);
     }
 
     #[test]
     fn test_new_robot() {
-        let result = Robot::new(Coord { x: 5, y: 6}, &vec![]);
-        assert_eq!(result.pos, Coord { x: 5, y: 6});
+        let result = new_robot(Coord { x: 5, y: 6}, &[]);
+        assert_eq!(result.get_pos(), Coord { x: 5, y: 6});
+        assert_eq!(result.get_dir(), Dir::Up);
     }
 }
@@ -1,12 +1,16 @@
-pub mod day11_cpu;
-pub mod day11_utils;
+use aoc2019_day11::day11_cpu;
+use aoc2019_day11::day11_render;
+use aoc2019_day11::day11_utils;
 
 use day11_cpu::*;
+use day11_render::*;
 use day11_utils::*;
 
 fn main() {
+    let options = RenderOptions::from_args();
     let input = aoc2019_utils::get_input("inputs/day11.txt");
     let prog = parse_prog(&input);
-    let grid = run_robot_sim(&prog, false);
+    let (grid, history) = run_robot_sim_with_history(&prog, false);
     println!("the robot painted {} tiles", grid.len());
+    do_renders(&options, &grid, &history, false);
 }
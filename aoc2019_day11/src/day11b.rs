@@ -1,13 +1,25 @@
-pub mod day11_cpu;
-pub mod day11_utils;
+use aoc2019_day11::day11_cpu;
+use aoc2019_day11::day11_render;
+use aoc2019_day11::day11_utils;
+
+use aoc2019_utils::ocr;
 
 use day11_cpu::*;
+use day11_render::*;
 use day11_utils::*;
 
 fn main() {
+    let options = RenderOptions::from_args();
     let input = aoc2019_utils::get_input("inputs/day11.txt");
     let prog = parse_prog(&input);
-    let grid = run_robot_sim(&prog, true);
+    let (grid, history) = run_robot_sim_with_history(&prog, true);
     println!("the robot painted {} tiles", grid.len());
-    print_grid(&grid);
+    do_renders(&options, &grid, &history, true);
+    match ocr::read_text(&to_image(&grid)) {
+        Ok(letters) => println!("registration identifier: {}", letters),
+        Err(err) => {
+            println!("couldn't read the registration identifier: {}", err);
+            print_grid(&grid);
+        },
+    }
 }
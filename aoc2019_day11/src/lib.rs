@@ -0,0 +1,33 @@
+pub mod day11_cpu;
+pub mod day11_render;
+pub mod day11_utils;
+
+use aoc2019_utils::ocr;
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day11_cpu::*;
+use day11_utils::*;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        11
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        run_robot_sim(prog, false).len().into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        let image = to_image(&run_robot_sim(prog, true));
+        let text = ocr::read_text(&image).ok();
+        Answer::image(image, text)
+    }
+}
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use aoc2019_utils::*;
+use aoc2019_utils::vec3d::Axis;
 
 pub type MoonPos = vec3d::Vec3d<i16>;
 pub type MoonVel = vec3d::Vec3d<i16>;
@@ -29,11 +30,7 @@ impl Moon {
     }
 
     pub fn apply_gravity(&mut self, other_pos: MoonPos) {
-        let mut to_other = other_pos - self.pos;
-        to_other.x /= if to_other.x != 0 { to_other.x.abs() } else { 1 };
-        to_other.y /= if to_other.y != 0 { to_other.y.abs() } else { 1 };
-        to_other.z /= if to_other.z != 0 { to_other.z.abs() } else { 1 };
-        self.vel += to_other;
+        self.vel += (other_pos - self.pos).signum();
     }
 
     pub fn apply_vel(&mut self) {
@@ -41,15 +38,11 @@ impl Moon {
     }
 
     pub fn get_potential(&self) -> i32 {
-        self.pos.x.abs() as i32
-            + self.pos.y.abs() as i32
-            + self.pos.z.abs() as i32
+        self.pos.map(i32::from).l1_norm()
     }
 
     pub fn get_kinetic(&self) -> i32 {
-        self.vel.x.abs() as i32
-            + self.vel.y.abs() as i32
-            + self.vel.z.abs() as i32
+        self.vel.map(i32::from).l1_norm()
     }
 
     pub fn get_energy(&self) -> i32 {
@@ -97,6 +90,69 @@ pub fn get_system_energy(moons: &Vec<Moon>) -> i32 {
     moons.iter().map(|moon| moon.get_energy()).sum()
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct Moon1d {
+    pos: i16,
+    vel: i16,
+}
+
+impl Moon1d {
+    fn apply_gravity(&mut self, other_pos: i16) {
+        self.vel += (other_pos - self.pos).signum();
+    }
+
+    fn apply_vel(&mut self) {
+        self.pos += self.vel;
+    }
+}
+
+fn create_moons_1d(positions: &[MoonPos], axis: Axis) -> Vec<Moon1d> {
+    positions.iter().map(|pos| {
+        Moon1d {
+            pos: pos[axis],
+            vel: 0,
+        }
+    }).collect()
+}
+
+fn sim_time_step_1d(moons: &mut Vec<Moon1d>) {
+    (0..moons.len()).for_each(|i| {
+        (0..moons.len()).for_each(|j| {
+            let moon_j_pos = { moons[j].pos };
+            moons[i].apply_gravity(moon_j_pos);
+        });
+    });
+
+    for moon in moons {
+        moon.apply_vel();
+    }
+}
+
+pub fn get_repeat_of_origin(moon_positions: &[MoonPos], axis: Axis) -> u64 {
+    let mut moons = create_moons_1d(moon_positions, axis);
+    let start_moons = moons.clone();
+
+    let mut step_count = 1u64;
+
+    loop {
+        sim_time_step_1d(&mut moons);
+
+        if moons == start_moons {
+            break;
+        }
+
+        step_count += 1;
+    }
+
+    step_count
+}
+
+pub fn get_first_repeat(moon_positions: &Vec<MoonPos>) -> u64 {
+    Axis::ALL.iter()
+        .map(|axis| get_repeat_of_origin(moon_positions, *axis))
+        .fold(1, lcm)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
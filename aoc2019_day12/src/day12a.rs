@@ -1,4 +1,4 @@
-pub mod day12_utils;
+use aoc2019_day12::day12_utils;
 
 use day12_utils::*;
 
@@ -0,0 +1,29 @@
+pub mod day12_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day12_utils::*;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Vec<MoonPos>;
+
+    fn get_day(&self) -> u32 {
+        12
+    }
+
+    fn parse(&self, input: &str) -> Vec<MoonPos> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, moon_positions: &Vec<MoonPos>) -> Answer {
+        let mut moons = create_moons(moon_positions);
+        (0..1000).for_each(|_| { sim_time_step(&mut moons); });
+        get_system_energy(&moons).into()
+    }
+
+    fn solve_b(&self, moon_positions: &Vec<MoonPos>) -> Answer {
+        get_first_repeat(moon_positions).into()
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::day13_utils::*;
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub fn render_frame(screen: &Screen, score: i64, status: &str) -> String {
+    let mut frame = String::with_capacity(
+        (screen.width() + 1) * (screen.height() + 2));
+
+    screen.rows().for_each(|row| {
+        row.iter().for_each(|tile| frame.push(tile.to_char()));
+        frame.push('\n');
+    });
+
+    frame.push_str(&format!("score: {:<10} blocks left: {:<6}\n",
+        score, get_num_blocks(screen)));
+    frame.push_str(status);
+    frame
+}
+
+/// Draws full frames to the terminal using ANSI escapes. Frames are paced so
+/// that at least `frame_delay` passes between the start of consecutive
+/// frames, regardless of how long the CPU took to produce each one.
+pub struct Renderer {
+    frame_delay: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl Renderer {
+    pub fn new(frame_delay: Duration) -> Self {
+        Self {
+            frame_delay,
+            last_frame: None,
+        }
+    }
+
+    pub fn set_frame_delay(&mut self, frame_delay: Duration) {
+        self.frame_delay = frame_delay;
+    }
+
+    pub fn begin(&mut self) {
+        print!("{}{}", CLEAR_SCREEN, HIDE_CURSOR);
+        self.last_frame = None;
+    }
+
+    pub fn draw(&mut self, screen: &Screen, score: i64, status: &str) {
+        if let Some(last_frame) = self.last_frame {
+            let elapsed = last_frame.elapsed();
+            if elapsed < self.frame_delay {
+                thread::sleep(self.frame_delay - elapsed);
+            }
+        }
+        self.last_frame = Some(Instant::now());
+
+        // Clear to the end of the screen after drawing so a shorter status
+        // line doesn't leave stale text behind.
+        print!("{}{}\x1b[J", CURSOR_HOME, render_frame(screen, score, status));
+        std::io::stdout().flush().unwrap();
+    }
+
+    pub fn end(&mut self) {
+        println!("{}", SHOW_CURSOR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_frame() {
+        let mut screen = Screen::new();
+        screen.set(0, 0, Tile::Wall);
+        screen.set(2, 1, Tile::Ball);
+        screen.set(4, 2, Tile::Empty);
+
+        let frame = render_frame(&screen, 42, "status");
+        let lines = frame.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "#    ");
+        assert_eq!(lines[1], "  o  ");
+        assert_eq!(lines[2], "     ");
+        assert!(lines[3].starts_with("score: 42 "));
+        assert_eq!(lines[4], "status");
+    }
+}
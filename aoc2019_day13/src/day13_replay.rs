@@ -0,0 +1,335 @@
+use std::fs;
+use std::str::FromStr;
+
+use crate::day13_utils::*;
+
+const REPLAY_HEADER: &str = "day13-replay v1";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TileChange {
+    pub coord: Coord,
+    pub old: Tile,
+    pub new: Tile,
+}
+
+/// One cycle of the game: the joystick input fed to the CPU (none for the
+/// very first cycle), the score after the cycle ran, and every tile that
+/// changed. Keeping the old tile in each change is what lets a player step
+/// backwards without re-simulating from the start.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ReplayFrame {
+    pub input: Option<i64>,
+    pub score: i64,
+    pub changes: Vec<TileChange>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Replay {
+    pub frames: Vec<ReplayFrame>,
+}
+
+fn parse_num<T: FromStr>(txt: &str, line_num: usize) -> Result<T, String> {
+    T::from_str(txt).map_err(|_| {
+        format!("line {}: bad number: {:?}", line_num, txt)
+    })
+}
+
+fn parse_tile(txt: &str, line_num: usize) -> Result<Tile, String> {
+    match parse_num::<i64>(txt, line_num)? {
+        num @ 0..=4 => Ok(Tile::from_num(num)),
+        num => Err(format!("line {}: bad tile num: {}", line_num, num)),
+    }
+}
+
+impl Replay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_frame(
+        &mut self,
+        input: Option<i64>,
+        score: i64,
+        prev_screen: &Screen,
+        screen: &Screen,
+    ) {
+        let changes = screen.tiles().iter().enumerate()
+            .map(|(idx, new)| (screen.get_coord_from_idx(idx), *new))
+            .map(|(coord, new)| {
+                TileChange {
+                    coord,
+                    old: prev_screen.get(coord.x as usize, coord.y as usize),
+                    new,
+                }
+            })
+            .filter(|change| change.old != change.new)
+            .collect();
+
+        self.frames.push(ReplayFrame { input, score, changes });
+    }
+
+    pub fn get_inputs(&self) -> Vec<i64> {
+        self.frames.iter().filter_map(|frame| frame.input).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut txt = String::new();
+        txt.push_str(REPLAY_HEADER);
+        txt.push('\n');
+
+        self.frames.iter().for_each(|frame| {
+            let input = match frame.input {
+                None => "-".to_string(),
+                Some(input) => input.to_string(),
+            };
+            txt.push_str(&format!("frame {} {}\n", input, frame.score));
+            frame.changes.iter().for_each(|change| {
+                txt.push_str(&format!("{},{} {} {}\n",
+                    change.coord.x, change.coord.y,
+                    change.old.to_num(), change.new.to_num()));
+            });
+        });
+
+        txt
+    }
+
+    pub fn from_text(txt: &str) -> Result<Self, String> {
+        let mut lines = txt.lines().enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, REPLAY_HEADER)) => {},
+            _ => return Err(format!("missing header: {:?}", REPLAY_HEADER)),
+        }
+
+        let mut replay = Replay::new();
+
+        for (line_num, line) in lines {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            if fields[0] == "frame" {
+                if fields.len() != 3 {
+                    return Err(format!("line {}: bad frame line", line_num));
+                }
+                let input = match fields[1] {
+                    "-" => None,
+                    input => Some(parse_num(input, line_num)?),
+                };
+                let score = parse_num(fields[2], line_num)?;
+                replay.frames.push(ReplayFrame {
+                    input,
+                    score,
+                    changes: vec![],
+                });
+                continue;
+            }
+
+            let frame = match replay.frames.last_mut() {
+                None => {
+                    return Err(format!(
+                        "line {}: tile change before first frame", line_num));
+                },
+                Some(frame) => frame,
+            };
+
+            let coord = fields[0].split(',').collect::<Vec<&str>>();
+            if fields.len() != 3 || coord.len() != 2 {
+                return Err(format!("line {}: bad tile change line", line_num));
+            }
+            let coord = Coord {
+                x: parse_num(coord[0], line_num)?,
+                y: parse_num(coord[1], line_num)?,
+            };
+            if coord.x < 0 || coord.y < 0 {
+                return Err(format!("line {}: negative tile coord", line_num));
+            }
+            frame.changes.push(TileChange {
+                coord,
+                old: parse_tile(fields[1], line_num)?,
+                new: parse_tile(fields[2], line_num)?,
+            });
+        }
+
+        Ok(replay)
+    }
+
+    pub fn save(&self, filename: &str) {
+        let err_msg = format!("Something went wrong writing the replay file: {}",
+            filename);
+        fs::write(filename, self.to_text()).expect(&err_msg);
+    }
+
+    pub fn load(filename: &str) -> Result<Self, String> {
+        let txt = fs::read_to_string(filename)
+            .map_err(|err| format!("{}: {}", filename, err))?;
+        Self::from_text(&txt)
+    }
+}
+
+/// Reconstructs the screen at any point in a replay. The position is the
+/// number of frames applied so far, so 0 is the blank screen before the game
+/// started and `num_frames()` is the end of the game.
+pub struct ReplayPlayer {
+    replay: Replay,
+    pos: usize,
+    screen: Screen,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            pos: 0,
+            screen: Screen::new(),
+        }
+    }
+
+    pub fn get_pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.replay.frames.len()
+    }
+
+    pub fn get_screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    pub fn get_score(&self) -> i64 {
+        match self.pos {
+            0 => 0,
+            pos => self.replay.frames[pos - 1].score,
+        }
+    }
+
+    pub fn get_last_input(&self) -> Option<i64> {
+        match self.pos {
+            0 => None,
+            pos => self.replay.frames[pos - 1].input,
+        }
+    }
+
+    pub fn step_forward(&mut self) -> bool {
+        if self.pos >= self.num_frames() {
+            return false;
+        }
+
+        let frame = &self.replay.frames[self.pos];
+        let screen = &mut self.screen;
+        frame.changes.iter().for_each(|change| {
+            screen.set(change.coord.x as usize, change.coord.y as usize, change.new);
+        });
+        self.pos += 1;
+        true
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.pos == 0 {
+            return false;
+        }
+
+        self.pos -= 1;
+        let frame = &self.replay.frames[self.pos];
+        let screen = &mut self.screen;
+        frame.changes.iter().for_each(|change| {
+            screen.set(change.coord.x as usize, change.coord.y as usize, change.old);
+        });
+        true
+    }
+
+    pub fn seek(&mut self, pos: usize) {
+        let pos = std::cmp::min(pos, self.num_frames());
+        while self.pos < pos {
+            self.step_forward();
+        }
+        while self.pos > pos {
+            self.step_back();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_replay() -> Replay {
+        let mut replay = Replay::new();
+        let blank = Screen::new();
+
+        let mut screen1 = blank.clone();
+        screen1.set(0, 0, Tile::Wall);
+        screen1.set(3, 2, Tile::Ball);
+        replay.record_frame(None, 0, &blank, &screen1);
+
+        // the second frame also grows the screen
+        let mut screen2 = screen1.clone();
+        screen2.set(3, 2, Tile::Empty);
+        screen2.set(4, 3, Tile::Ball);
+        replay.record_frame(Some(-1), 12, &screen1, &screen2);
+
+        replay
+    }
+
+    #[test]
+    fn test_record_frame() {
+        let replay = make_replay();
+        assert_eq!(replay.frames.len(), 2);
+        assert_eq!(replay.frames[0].changes.len(), 2);
+        assert_eq!(replay.frames[1].changes, vec![
+            TileChange {
+                coord: Coord { x: 3, y: 2 },
+                old: Tile::Ball,
+                new: Tile::Empty,
+            },
+            TileChange {
+                coord: Coord { x: 4, y: 3 },
+                old: Tile::Empty,
+                new: Tile::Ball,
+            },
+        ]);
+        assert_eq!(replay.get_inputs(), vec![-1]);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let replay = make_replay();
+        let txt = replay.to_text();
+        assert!(txt.starts_with("day13-replay v1\nframe - 0\n"));
+        assert_eq!(Replay::from_text(&txt), Ok(replay));
+    }
+
+    #[test]
+    fn test_from_text_errors() {
+        assert!(Replay::from_text("").is_err());
+        assert!(Replay::from_text("day13-replay v1\n1,2 0 1\n").is_err());
+        assert!(Replay::from_text("day13-replay v1\nframe x 0\n").is_err());
+        assert!(Replay::from_text("day13-replay v1\nframe - 0\n1,2 0 9\n").is_err());
+        assert!(Replay::from_text("day13-replay v1\nframe - 0\n-1,0 0 1\n").is_err());
+    }
+
+    #[test]
+    fn test_player_scrub() {
+        let mut player = ReplayPlayer::new(make_replay());
+        assert_eq!(player.get_pos(), 0);
+        assert!(!player.step_back());
+
+        assert!(player.step_forward());
+        assert!(player.step_forward());
+        assert!(!player.step_forward());
+        assert_eq!(player.get_score(), 12);
+        assert_eq!(player.get_last_input(), Some(-1));
+        assert_eq!(get_ball_coord(player.get_screen()), Some(Coord { x: 4, y: 3 }));
+
+        assert!(player.step_back());
+        assert_eq!(player.get_score(), 0);
+        assert_eq!(get_ball_coord(player.get_screen()), Some(Coord { x: 3, y: 2 }));
+
+        player.seek(0);
+        assert_eq!(get_ball_coord(player.get_screen()), None);
+        assert_eq!(player.get_screen().get(0, 0), Tile::Empty);
+
+        player.seek(100);
+        assert_eq!(player.get_pos(), 2);
+    }
+}
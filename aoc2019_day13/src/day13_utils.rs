@@ -0,0 +1,270 @@
+use aoc2019_utils::*;
+
+use crate::day13_cpu::*;
+
+pub type Coord = point_2d::Point2d<i16>;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Tile { #[default] Empty, Wall, Block, Paddle, Ball }
+
+impl Tile {
+    pub fn from_num(num: i64) -> Self {
+        match num {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            _ => panic!("bad tile num"),
+        }
+    }
+
+    pub fn to_num(self) -> i64 {
+        match self {
+            Tile::Empty => 0,
+            Tile::Wall => 1,
+            Tile::Block => 2,
+            Tile::Paddle => 3,
+            Tile::Ball => 4,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '=',
+            Tile::Paddle => '-',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+/// The arcade screen. It starts out empty and grows to fit whatever the
+/// cabinet program draws, so boards of any size work.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Screen {
+    tiles: grid::Grid<Tile>,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn width(&self) -> usize {
+        self.tiles.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.height()
+    }
+
+    /// Every tile, row by row.
+    pub fn tiles(&self) -> &[Tile] {
+        self.tiles.cells()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        self.tiles.rows()
+    }
+
+    pub fn get_coord_idx(&self, x: usize, y: usize) -> usize {
+        (y * self.width()) + x
+    }
+
+    pub fn get_coord_from_idx(&self, idx: usize) -> Coord {
+        Coord {
+            x: (idx % self.width()) as i16,
+            y: (idx / self.width()) as i16,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Tile {
+        self.tiles.get(to_grid_coord(x, y)).copied().unwrap_or(Tile::Empty)
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, tile: Tile) {
+        if x >= self.width() || y >= self.height() {
+            self.resize(
+                std::cmp::max(self.width(), x + 1),
+                std::cmp::max(self.height(), y + 1),
+            );
+        }
+        self.tiles[to_grid_coord(x, y)] = tile;
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        let mut tiles = grid::Grid::new(width, height, Tile::Empty);
+        self.tiles.iter().for_each(|(coord, tile)| tiles[coord] = *tile);
+        self.tiles = tiles;
+    }
+}
+
+fn to_grid_coord(x: usize, y: usize) -> grid::GridCoord {
+    grid::GridCoord { x: x as i32, y: y as i32 }
+}
+
+fn is_score_sentinel(x: i64, y: i64) -> bool {
+    x == -1 && y == 0
+}
+
+pub fn run_cycle(cpu: &mut Cpu, screen: &mut Screen) -> (bool, Option<i64>) {
+    cpu.exec_prog();
+
+    let mut score = None;
+
+    while cpu.has_output() {
+        let x = cpu.pop_output().unwrap();
+        let y = cpu.pop_output().expect("incomplete draw command");
+        let data = cpu.pop_output().expect("incomplete draw command");
+
+        if is_score_sentinel(x, y) {
+            score = Some(data);
+        } else if x >= 0 && y >= 0 {
+            screen.set(x as usize, y as usize, Tile::from_num(data));
+        } else {
+            panic!("bad draw command: x: {}, y: {}, data: {}", x, y, data);
+        }
+    }
+
+    let cont = cpu.get_state() != CpuState::Done;
+    (cont, score)
+}
+
+pub fn get_num_blocks(screen: &Screen) -> usize {
+    screen.tiles().iter().filter(|tile| **tile == Tile::Block).count()
+}
+
+pub fn get_ball_coord(screen: &Screen) -> Option<Coord> {
+    screen.tiles().iter()
+        .position(|tile| *tile == Tile::Ball)
+        .map(|idx| screen.get_coord_from_idx(idx))
+}
+
+pub fn get_paddle_center(screen: &Screen) -> Coord {
+    let paddle_coords = screen.tiles().iter().enumerate()
+        .filter(|(_, tile)| **tile == Tile::Paddle)
+        .map(|(i, _)| {
+            screen.get_coord_from_idx(i)
+        })
+        .collect::<Vec<Coord>>();
+
+    paddle_coords.iter()
+        .fold(Coord { x: 0, y: 0 }, |acc, coord| {
+            acc + *coord
+        })
+        / paddle_coords.len() as i16
+}
+
+pub fn get_next_input(ball: Coord, paddle_center: Coord) -> i64 {
+    if ball.x < paddle_center.x {
+        -1
+    } else if ball.x > paddle_center.x {
+        1
+    } else {
+        0
+    }
+}
+
+pub fn count_blocks(prog: &Vec<i64>) -> usize {
+    let mut cpu = Cpu::new(prog);
+    cpu.set_print_output(false);
+
+    let mut screen = Screen::new();
+    run_cycle(&mut cpu, &mut screen);
+    get_num_blocks(&screen)
+}
+
+pub fn play_game(prog: &[i64]) -> i64 {
+    let mut prog = prog.to_vec();
+    prog[0] = 2;
+
+    let mut cpu = Cpu::new(&prog);
+    cpu.set_print_output(false);
+
+    let mut screen = Screen::new();
+    let mut score = 0;
+
+    loop {
+        let (cont, new_score) = run_cycle(&mut cpu, &mut screen);
+        score = new_score.unwrap_or(score);
+        if !cont {
+            return score;
+        }
+
+        cpu.add_input(get_next_input(
+            get_ball_coord(&screen).unwrap(),
+            get_paddle_center(&screen),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_num_round_trip() {
+        (0..=4).for_each(|num| {
+            assert_eq!(Tile::from_num(num).to_num(), num);
+        });
+    }
+
+    #[test]
+    fn test_screen_grows() {
+        let mut screen = Screen::new();
+        assert_eq!((screen.width(), screen.height()), (0, 0));
+        assert_eq!(screen.get(3, 3), Tile::Empty);
+
+        screen.set(2, 1, Tile::Ball);
+        assert_eq!((screen.width(), screen.height()), (3, 2));
+        assert_eq!(screen.get(2, 1), Tile::Ball);
+
+        screen.set(0, 4, Tile::Wall);
+        screen.set(5, 0, Tile::Block);
+        assert_eq!((screen.width(), screen.height()), (6, 5));
+        assert_eq!(screen.get(2, 1), Tile::Ball);
+        assert_eq!(screen.get(0, 4), Tile::Wall);
+        assert_eq!(screen.get(5, 0), Tile::Block);
+        assert_eq!(screen.tiles().len(), 30);
+    }
+
+    #[test]
+    fn test_screen_coord_idx() {
+        let mut screen = Screen::new();
+        screen.set(39, 19, Tile::Empty);
+        assert_eq!(screen.get_coord_idx(3, 2), 83);
+        assert_eq!(screen.get_coord_from_idx(83), Coord { x: 3, y: 2 });
+    }
+
+    #[test]
+    fn test_run_cycle() {
+        // draws a wall at (5, 1), a ball at (0, 3) and sets the score to 7
+        let prog = parse_prog("104,5,104,1,104,1,104,0,104,3,104,4,104,-1,104,0,104,7,99");
+        let mut cpu = Cpu::new(&prog);
+        cpu.set_print_output(false);
+        let mut screen = Screen::new();
+        assert_eq!(run_cycle(&mut cpu, &mut screen), (false, Some(7)));
+        assert_eq!((screen.width(), screen.height()), (6, 4));
+        assert_eq!(screen.get(5, 1), Tile::Wall);
+        assert_eq!(get_ball_coord(&screen), Some(Coord { x: 0, y: 3 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "bad draw command")]
+    fn test_run_cycle_bad_sentinel() {
+        let prog = parse_prog("104,-1,104,1,104,7,99");
+        let mut cpu = Cpu::new(&prog);
+        cpu.set_print_output(false);
+        run_cycle(&mut cpu, &mut Screen::new());
+    }
+
+    #[test]
+    fn test_get_next_input() {
+        let paddle = Coord { x: 5, y: 20 };
+        assert_eq!(get_next_input(Coord { x: 3, y: 10 }, paddle), -1);
+        assert_eq!(get_next_input(Coord { x: 5, y: 10 }, paddle), 0);
+        assert_eq!(get_next_input(Coord { x: 8, y: 10 }, paddle), 1);
+    }
+}
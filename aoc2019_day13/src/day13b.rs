@@ -1,104 +1,52 @@
-pub mod day13_cpu;
+use aoc2019_day13::day13_cpu;
+use aoc2019_day13::day13_render;
+use aoc2019_day13::day13_replay;
+use aoc2019_day13::day13_utils;
+
+use std::str::FromStr;
+use std::time::Duration;
 
 use aoc2019_utils::*;
 
 use day13_cpu::*;
+use day13_render::*;
+use day13_replay::*;
+use day13_utils::*;
 
-const SCREEN_WIDTH: usize = 34;
-const SCREEN_HEIGHT: usize = 34;
-
-type Coord = point_2d::Point2d<i16>;
-type Screen = Vec<Tile>;
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Tile { Empty, Wall, Block, Paddle, Ball }
-
-impl Tile {
-    fn from_num(num: i64) -> Self {
-        match num {
-            0 => Tile::Empty,
-            1 => Tile::Wall,
-            2 => Tile::Block,
-            3 => Tile::Paddle,
-            4 => Tile::Ball,
-            _ => panic!("bad tile num"),
-        }
-    }
-}
-
-fn get_coord_idx(x: i64, y: i64) -> usize {
-    (y as usize * SCREEN_HEIGHT) + x as usize
-}
+const DEFAULT_FRAME_MS: u64 = 30;
 
-fn get_coord_from_idx(idx: usize) -> Coord {
-    Coord {
-        x: (idx % SCREEN_WIDTH) as i16,
-        y: (idx / SCREEN_WIDTH) as i16,
-    }
+struct Options {
+    render: bool,
+    frame_delay: Duration,
+    record_file: Option<String>,
 }
 
-fn run_cycle(cpu: &mut Cpu, screen: &mut Screen) -> (bool, i64) {
-    cpu.exec_prog();
-
-    let mut score = 0;
-
-    while cpu.has_output() {
-        let x = cpu.pop_output().unwrap();
-        let y = cpu.pop_output().unwrap();
-        let data = cpu.pop_output().unwrap();
+fn get_options() -> Options {
+    let mut options = Options {
+        render: false,
+        frame_delay: Duration::from_millis(DEFAULT_FRAME_MS),
+        record_file: None,
+    };
 
-        if x >= 0 {
-            screen[get_coord_idx(x, y)] = Tile::from_num(data);
-        } else {
-            score = data;
+    parse_args("--render, --frame-ms <ms>, --record <file>", |arg, next_arg| {
+        match arg {
+            "--render" => options.render = true,
+            "--frame-ms" => {
+                let ms = u64::from_str(&next_arg()).expect("bad --frame-ms value");
+                options.frame_delay = Duration::from_millis(ms);
+            },
+            "--record" => options.record_file = Some(next_arg()),
+            _ => return false,
         }
-    }
-
-    let cont = cpu.get_state() != CpuState::Done;
-    (cont, score)
-}
-
-fn get_num_blocks(screen: &Screen) -> usize {
-    screen.iter().filter(|tile| **tile == Tile::Block).count()
-}
+        true
+    });
 
-fn get_ball_coord(screen: &Screen) -> Option<Coord> {
-    screen.iter().enumerate()
-        .filter(|(_, tile)| **tile == Tile::Ball)
-        .next()
-        .map(|(i, _)| {
-            get_coord_from_idx(i)
-        })
-}
-
-fn get_paddle_center(screen: &Screen) -> Coord {
-    let paddle_coords = screen.iter().enumerate()
-        .filter(|(_, tile)| **tile == Tile::Paddle)
-        .map(|(i, _)| {
-            get_coord_from_idx(i)
-        })
-        .collect::<Vec<Coord>>();
-
-    let avg = paddle_coords.iter()
-        .fold(Coord { x: 0, y: 0 }, |acc, coord| {
-            acc + *coord
-        })
-        / paddle_coords.len() as i16;
-
-    avg
-}
-
-fn get_next_input(ball: Coord, paddle_center: Coord) -> i64 {
-    if ball.x < paddle_center.x {
-        -1
-    } else if ball.x > paddle_center.x {
-        1
-    } else {
-        0
-    }
+    options
 }
 
 fn main() {
+    let options = get_options();
+
     let input = get_input("inputs/day13.txt");
     let prog = {
         let mut prog = parse_prog(&input);
@@ -108,15 +56,35 @@ fn main() {
     let mut cpu = Cpu::new(&prog);
     cpu.set_print_output(false);
 
-    let mut screen = vec![Tile::Empty; SCREEN_WIDTH * SCREEN_HEIGHT];
+    let mut screen = Screen::new();
+    let mut prev_screen = screen.clone();
+    let mut score = 0;
+    let mut last_input = None;
+    let mut replay = Replay::new();
+    let mut renderer = Renderer::new(options.frame_delay);
 
-    println!("");
+    if options.render {
+        renderer.begin();
+    } else {
+        println!();
+    }
 
     loop {
-        let (cont, score) = run_cycle(&mut cpu, &mut screen);
+        let (cont, new_score) = run_cycle(&mut cpu, &mut screen);
+        score = new_score.unwrap_or(score);
         let num_blocks = get_num_blocks(&screen);
-        print!("\rblocks left: {}, score: {}                               ",
-            num_blocks, score);
+
+        if options.record_file.is_some() {
+            replay.record_frame(last_input, score, &prev_screen, &screen);
+            prev_screen.clone_from(&screen);
+        }
+
+        if options.render {
+            renderer.draw(&screen, score, "");
+        } else {
+            print!("\rblocks left: {}, score: {}                               ",
+                num_blocks, score);
+        }
 
         if !cont {
             break;
@@ -128,7 +96,17 @@ fn main() {
         );
 
         cpu.add_input(input);
+        last_input = Some(input);
     }
 
-    println!("");
+    if options.render {
+        renderer.end();
+    } else {
+        println!();
+    }
+
+    if let Some(record_file) = options.record_file {
+        replay.save(&record_file);
+        println!("recorded {} frames to {}", replay.frames.len(), record_file);
+    }
 }
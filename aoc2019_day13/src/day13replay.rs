@@ -0,0 +1,87 @@
+use aoc2019_day13::day13_render;
+use aoc2019_day13::day13_replay;
+
+use std::io::BufRead;
+use std::str::FromStr;
+use std::time::Duration;
+
+use day13_render::*;
+use day13_replay::*;
+
+const DEFAULT_FRAME_MS: u64 = 30;
+
+const HELP: &str = "commands: n [k] (forward k frames), p [k] (back k frames), \
+    g <frame> (go to frame), play, rewind, ms <frame ms>, q";
+
+fn get_count(arg: Option<&str>) -> Option<usize> {
+    match arg {
+        None => Some(1),
+        Some(arg) => usize::from_str(arg).ok(),
+    }
+}
+
+fn draw(renderer: &mut Renderer, player: &ReplayPlayer, msg: &str) {
+    let input = match player.get_last_input() {
+        None => "-".to_string(),
+        Some(input) => input.to_string(),
+    };
+    let status = format!("frame {}/{}, joystick: {}\n{}\n> ",
+        player.get_pos(), player.num_frames(), input, msg);
+    renderer.draw(player.get_screen(), player.get_score(), &status);
+}
+
+fn main() {
+    let filename = std::env::args().nth(1)
+        .expect("usage: aoc2019_day13replay <replay file>");
+    let replay = match Replay::load(&filename) {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("couldn't load replay: {}", err);
+            std::process::exit(1);
+        },
+    };
+
+    let mut player = ReplayPlayer::new(replay);
+    let mut renderer = Renderer::new(Duration::from_millis(DEFAULT_FRAME_MS));
+    renderer.begin();
+    player.step_forward();
+    draw(&mut renderer, &player, HELP);
+
+    let stdin = std::io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.unwrap();
+        let mut words = line.split_whitespace();
+        let cmd = words.next().unwrap_or("n");
+        let arg = words.next();
+
+        let mut msg = String::new();
+        match (cmd, get_count(arg)) {
+            ("q", _) => break,
+            ("n", Some(count)) => {
+                (0..count).for_each(|_| { player.step_forward(); });
+            },
+            ("p", Some(count)) => {
+                (0..count).for_each(|_| { player.step_back(); });
+            },
+            ("g", Some(pos)) if arg.is_some() => player.seek(pos),
+            ("play", _) => {
+                while player.step_forward() {
+                    draw(&mut renderer, &player, "playing...");
+                }
+            },
+            ("rewind", _) => {
+                while player.step_back() {
+                    draw(&mut renderer, &player, "rewinding...");
+                }
+            },
+            ("ms", Some(ms)) if arg.is_some() => {
+                renderer.set_frame_delay(Duration::from_millis(ms as u64));
+            },
+            _ => msg = HELP.to_string(),
+        }
+
+        draw(&mut renderer, &player, &msg);
+    }
+
+    renderer.end();
+}
@@ -0,0 +1,31 @@
+pub mod day13_cpu;
+pub mod day13_render;
+pub mod day13_replay;
+pub mod day13_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day13_cpu::*;
+use day13_utils::*;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        13
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        count_blocks(prog).into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        play_game(prog).into()
+    }
+}
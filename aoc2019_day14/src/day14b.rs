@@ -1,4 +1,4 @@
-pub mod day14_utils;
+use aoc2019_day14::day14_utils;
 
 use day14_utils::*;
 
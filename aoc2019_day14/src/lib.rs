@@ -0,0 +1,31 @@
+pub mod day14_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day14_utils::*;
+
+const COLLECTED_ORE: usize = 1000000000000;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Reactions;
+
+    fn get_day(&self) -> u32 {
+        14
+    }
+
+    fn parse(&self, input: &str) -> Reactions {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, reactions: &Reactions) -> Answer {
+        let depths = build_depths(reactions);
+        get_needed_ore_for_fuel(1, reactions, &depths).into()
+    }
+
+    fn solve_b(&self, reactions: &Reactions) -> Answer {
+        let depths = build_depths(reactions);
+        get_max_fuel_for_ore(COLLECTED_ORE, reactions, &depths).into()
+    }
+}
@@ -1,6 +1,8 @@
 use std::convert::TryInto;
 use std::str::FromStr;
 
+use aoc2019_utils::grid_robot::IntcodeMachine;
+
 const ADD_OP: i64 = 1;
 const MUL_OP: i64 = 2;
 const READ_OP: i64 = 3;
@@ -19,6 +21,7 @@ pub enum CpuState {
     WaitOnInput,
 }
 
+#[derive(Clone)]
 pub struct Cpu {
     input: Vec<i64>,
     mem: Vec<i64>,
@@ -38,12 +41,12 @@ enum ParamMode {
 impl Cpu {
     const MEM_SIZE: usize = 1024 * 1024;
 
-    pub fn new(prog: &Vec<i64>) -> Cpu {
-        let mut mem = vec![0; Self::MEM_SIZE];
-        mem[..prog.len()].copy_from_slice(&prog);
+    // Memory only grows as far as the program writes, so a CPU stays cheap to
+    // clone. Anything past that reads as 0.
+    pub fn new(prog: &[i64]) -> Cpu {
         Cpu {
             input: vec![],
-            mem: mem,
+            mem: prog.to_vec(),
             instr_ptr: 0,
             print_output: true,
             output: vec![],
@@ -103,17 +106,30 @@ impl Cpu {
         }
     }
 
+    fn read_mem(&self, loc: usize) -> i64 {
+        assert!(loc < Self::MEM_SIZE, "address out of range: {}", loc);
+        self.mem.get(loc).copied().unwrap_or(0)
+    }
+
+    fn write_mem(&mut self, loc: usize, val: i64) {
+        assert!(loc < Self::MEM_SIZE, "address out of range: {}", loc);
+        if loc >= self.mem.len() {
+            self.mem.resize(loc + 1, 0);
+        }
+        self.mem[loc] = val;
+    }
+
     fn get_param_val(&self, modes: u32, param_num: u32) -> i64 {
-        let param = self.mem[self.instr_ptr + param_num as usize];
+        let param = self.read_mem(self.instr_ptr + param_num as usize);
         match Self::get_param_mode(modes, param_num) {
-            ParamMode::Register => self.mem[param as usize],
+            ParamMode::Register => self.read_mem(param as usize),
             ParamMode::Immediate => param,
-            ParamMode::Relative => self.mem[(param + self.relative_base) as usize],
+            ParamMode::Relative => self.read_mem((param + self.relative_base) as usize),
         }
     }
 
     fn get_dest_loc(&self, modes: u32, param_num: u32) -> usize {
-        let param = self.mem[self.instr_ptr + param_num as usize];
+        let param = self.read_mem(self.instr_ptr + param_num as usize);
         match Self::get_param_mode(modes, param_num) {
             ParamMode::Register => param as usize,
             ParamMode::Relative => (param + self.relative_base) as usize,
@@ -125,14 +141,14 @@ impl Cpu {
         let param1 = self.get_param_val(modes, 1);
         let param2 = self.get_param_val(modes, 2);
         let dest = self.get_dest_loc(modes, 3);
-        self.mem[dest] = param1 + param2;
+        self.write_mem(dest, param1 + param2);
     }
 
     fn do_mul(&mut self, modes: u32) {
         let param1 = self.get_param_val(modes, 1);
         let param2 = self.get_param_val(modes, 2);
         let dest = self.get_dest_loc(modes, 3);
-        self.mem[dest] = param1 * param2;
+        self.write_mem(dest, param1 * param2);
     }
 
     fn do_read(&mut self, modes: u32) -> bool {
@@ -141,7 +157,8 @@ impl Cpu {
         }
 
         let dest = self.get_dest_loc(modes, 1);
-        self.mem[dest as usize] = self.input.remove(0);
+        let input = self.input.remove(0);
+        self.write_mem(dest, input);
         true
     }
 
@@ -176,14 +193,14 @@ impl Cpu {
         let param1 = self.get_param_val(modes, 1);
         let param2 = self.get_param_val(modes, 2);
         let dest = self.get_dest_loc(modes, 3);
-        self.mem[dest] = if param1 < param2 { 1 } else { 0 };
+        self.write_mem(dest, if param1 < param2 { 1 } else { 0 });
     }
 
     fn do_eq(&mut self, modes: u32) {
         let param1 = self.get_param_val(modes, 1);
         let param2 = self.get_param_val(modes, 2);
         let dest = self.get_dest_loc(modes, 3);
-        self.mem[dest] = if param1 == param2 { 1 } else { 0 };
+        self.write_mem(dest, if param1 == param2 { 1 } else { 0 });
     }
 
     fn do_adj_rel_base(&mut self, modes: u32) {
@@ -205,7 +222,7 @@ impl Cpu {
 
     pub fn exec(&mut self) -> CpuState {
         if self.state == CpuState::Done
-            || self.instr_ptr >= self.mem.len()
+            || self.instr_ptr >= Self::MEM_SIZE
         {
             self.state = CpuState::Done;
             return self.state;
@@ -213,7 +230,7 @@ impl Cpu {
 
         self.state = CpuState::Running;
 
-        let instr_val = self.mem[self.instr_ptr];
+        let instr_val = self.read_mem(self.instr_ptr);
         let op = instr_val % 100;
         let modes = Self::extract_modes(instr_val);
 
@@ -264,7 +281,7 @@ impl Cpu {
         };
 
         self.instr_ptr += instr_len;
-        if self.instr_ptr >= self.mem.len() {
+        if self.instr_ptr >= Self::MEM_SIZE {
             self.state = CpuState::Done;
         }
 
@@ -276,6 +293,24 @@ impl Cpu {
     }
 }
 
+impl IntcodeMachine for Cpu {
+    fn add_input(&mut self, input: i64) {
+        Cpu::add_input(self, input);
+    }
+
+    fn run(&mut self) {
+        self.exec_prog();
+    }
+
+    fn pop_output(&mut self) -> Option<i64> {
+        Cpu::pop_output(self)
+    }
+
+    fn is_done(&self) -> bool {
+        self.state == CpuState::Done
+    }
+}
+
 pub fn parse_prog(instr_txt: &str) -> Vec<i64> {
     instr_txt
         .split(",")
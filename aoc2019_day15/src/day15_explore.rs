@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+
+use aoc2019_utils::grid_robot::WorldModel;
+use aoc2019_utils::search;
+use aoc2019_utils::search::SearchTree;
+
+use crate::day15_utils::*;
+
+pub struct ExploreResult {
+    pub ship_map: ShipMap,
+    pub start_pos: Coord,
+    /// Shortest paths from the start to every tile the droids reached.
+    pub path_tree: SearchTree<Coord>,
+    /// Movement commands sent to droid CPUs, across every droid used.
+    pub num_moves: usize,
+}
+
+pub trait MapExplorer {
+    fn get_name(&self) -> &'static str;
+    fn explore(&self, prog: &[i64]) -> ExploreResult;
+}
+
+/// The original explorer: one droid that keeps walking to the closest unknown
+/// tile, backtracking through tiles it has already seen.
+pub struct WalkingExplorer;
+
+impl MapExplorer for WalkingExplorer {
+    fn get_name(&self) -> &'static str {
+        "walking"
+    }
+
+    fn explore(&self, prog: &[i64]) -> ExploreResult {
+        let (ship_map, start_pos, num_moves) = walk_map(prog);
+        let path_tree = search::flood_fill(&ship_map, start_pos);
+        ExploreResult { ship_map, start_pos, path_tree, num_moves }
+    }
+}
+
+/// Explores in true BFS order. Every droid only ever probes the tiles next
+/// to it. When a probe finds an open tile, the droid is forked (CPU state
+/// included, but not its history) to stand on that tile and joins the
+/// frontier, and the original steps back. Nothing ever backtracks more than
+/// one tile.
+pub struct ForkingExplorer;
+
+impl MapExplorer for ForkingExplorer {
+    fn get_name(&self) -> &'static str {
+        "forking"
+    }
+
+    fn explore(&self, prog: &[i64]) -> ExploreResult {
+        let robot = new_robot(prog);
+        let start_pos = robot.get_pos();
+
+        let mut ship_map = ShipMap::new();
+        ship_map.update_tile(start_pos, Tile::Space);
+
+        let mut num_moves = 0;
+        let mut frontier = VecDeque::new();
+        frontier.push_back(robot);
+
+        while let Some(mut robot) = frontier.pop_front() {
+            let at = robot.get_pos();
+
+            for dir in SEARCH_DIRS.iter() {
+                if ship_map.get_cell(dir.move_coord(at)) != Tile::Unknown {
+                    continue;
+                }
+
+                let moved = robot.step(&mut ship_map, *dir)
+                    .unwrap_or_else(|err| panic!("bad droid program: {}", err));
+                num_moves += 1;
+                if !moved.moved {
+                    continue;
+                }
+
+                frontier.push_back(robot.fork());
+
+                robot.step(&mut ship_map, dir.reverse())
+                    .unwrap_or_else(|err| panic!("bad droid program: {}", err));
+                num_moves += 1;
+            }
+        }
+
+        let path_tree = search::flood_fill(&ship_map, start_pos);
+        ExploreResult { ship_map, start_pos, path_tree, num_moves }
+    }
+}
+
+pub fn get_explorers() -> Vec<Box<dyn MapExplorer>> {
+    vec![Box::new(WalkingExplorer), Box::new(ForkingExplorer)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day15_cpu::parse_prog;
+
+    fn make_map(rows: &[&str]) -> ShipMap {
+        let mut ship_map = ShipMap::new();
+        rows.iter().enumerate().for_each(|(y, row)| {
+            row.chars().enumerate().for_each(|(x, c)| {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    'O' => Tile::Oxygen,
+                    _ => Tile::Space,
+                };
+                ship_map.update_tile(Coord { x: x as i32, y: y as i32 }, tile);
+            });
+        });
+        ship_map
+    }
+
+    // A droid in a 7x7 maze, starting at (1, 1) with the oxygen system at
+    // (3, 3). It keeps its position as an index into the maze, which starts at
+    // address 53, and looks up move offsets in the table at 48.
+    const DROID_PROG: &str = concat!(
+        "3,42,9,42,1201,48,0,43,1002,42,-1,44,9,44,1,47,43,45,9,45,1201,53,0,46,",
+        "1002,45,-1,44,9,44,4,46,1006,46,0,1001,45,0,47,1105,1,0,",
+        "0,0,0,0,0,8,0,-7,7,-1,1,",
+        "0,0,0,0,0,0,0,",
+        "0,1,1,1,1,1,0,",
+        "0,1,0,0,0,1,0,",
+        "0,1,0,2,1,1,0,",
+        "0,1,0,0,0,1,0,",
+        "0,1,1,1,1,1,0,",
+        "0,0,0,0,0,0,0",
+    );
+
+    #[test]
+    fn test_explorers_agree() {
+        let prog = parse_prog(DROID_PROG);
+        let results = get_explorers().iter()
+            .map(|explorer| explorer.explore(&prog))
+            .collect::<Vec<ExploreResult>>();
+
+        let expected = &results[0];
+        // Every wall but the outer corners gets probed.
+        assert_eq!(expected.ship_map.tiles.len(), 18 + 27);
+        assert_eq!(expected.ship_map.get_oxygen_pos(), Some(Coord { x: 2, y: 2 }));
+        assert_eq!(expected.path_tree.len(), 18);
+        assert_eq!(expected.path_tree.get_cost(&Coord { x: 2, y: 2 }), Some(8));
+        results.iter().for_each(|result| {
+            assert_eq!(result.ship_map, expected.ship_map);
+            assert_eq!(result.path_tree, expected.path_tree);
+        });
+    }
+
+    #[test]
+    fn test_flood_fill_ship_map() {
+        let ship_map = make_map(&[
+            "#####",
+            "#  O#",
+            "# ###",
+            "#   #",
+            "#####",
+        ]);
+        let root = Coord { x: 1, y: 1 };
+        let tree = search::flood_fill(&ship_map, root);
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.get_cost(&Coord { x: 3, y: 1 }), Some(2));
+        assert_eq!(tree.get_cost(&Coord { x: 3, y: 3 }), Some(4));
+        assert_eq!(tree.get_cost(&Coord { x: 0, y: 0 }), None);
+        assert_eq!(tree.get_max_cost(), 4);
+        assert_eq!(tree.get_path_to(&Coord { x: 3, y: 3 }), Some(vec![
+            Coord { x: 1, y: 1 },
+            Coord { x: 1, y: 2 },
+            Coord { x: 1, y: 3 },
+            Coord { x: 2, y: 3 },
+            Coord { x: 3, y: 3 },
+        ]));
+        assert_eq!(tree.get_path_to(&root), Some(vec![root]));
+        assert_eq!(tree.get_path_to(&Coord { x: 0, y: 0 }), None);
+    }
+}
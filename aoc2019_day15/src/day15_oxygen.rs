@@ -0,0 +1,337 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::day15_utils::*;
+
+const FILLED_CHAR: char = 'O';
+const FRONTIER_CHAR: char = 'o';
+const SOURCE_CHAR: char = '@';
+const LEAK_CHAR: char = 'x';
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DiffusionStep {
+    pub minute: u32,
+    /// Tiles that got oxygen this minute, in reading order.
+    pub frontier: Vec<Coord>,
+    /// Leaks that oxygen reached this minute.
+    pub leaking: Vec<Coord>,
+    pub num_filled: usize,
+}
+
+/// Minute-by-minute oxygen spread over an explored ship. Oxygen starts at
+/// every source and each minute moves into every open neighbour of a filled
+/// tile. Leaks are open tiles that never hold oxygen: whatever flows into one
+/// is lost, so oxygen can't pass through it.
+pub struct OxygenSim<'a> {
+    ship_map: &'a ShipMap,
+    sources: HashSet<Coord>,
+    leaks: HashSet<Coord>,
+    filled: HashSet<Coord>,
+    frontier: Vec<Coord>,
+    leaked: HashSet<Coord>,
+    minute: u32,
+}
+
+fn sort_reading_order(coords: &mut [Coord]) {
+    coords.sort_by_key(|coord| (coord.y, coord.x));
+}
+
+fn is_open_tile(ship_map: &ShipMap, pos: Coord) -> bool {
+    match ship_map.get_tile_at(pos) {
+        Tile::Space | Tile::Oxygen => true,
+        Tile::Wall | Tile::Unknown => false,
+    }
+}
+
+fn check_open(ship_map: &ShipMap, coords: &[Coord], kind: &str) -> Result<(), String> {
+    match coords.iter().find(|pos| !is_open_tile(ship_map, **pos)) {
+        Some(pos) => Err(format!("{} at {},{} isn't on an open tile ({:?})",
+            kind, pos.x, pos.y, ship_map.get_tile_at(*pos))),
+        None => Ok(()),
+    }
+}
+
+impl<'a> OxygenSim<'a> {
+    /// Fails if a source or leak is on a wall or a tile the droid never
+    /// explored.
+    pub fn new(ship_map: &'a ShipMap, sources: &[Coord], leaks: &[Coord])
+        -> Result<Self, String>
+    {
+        check_open(ship_map, sources, "source")?;
+        check_open(ship_map, leaks, "leak")?;
+
+        let leaks = leaks.iter().copied().collect::<HashSet<Coord>>();
+        let mut frontier = sources.iter().copied()
+            .filter(|pos| !leaks.contains(pos))
+            .collect::<Vec<Coord>>();
+        sort_reading_order(&mut frontier);
+        frontier.dedup();
+
+        Ok(Self {
+            ship_map,
+            sources: sources.iter().copied().collect(),
+            leaks,
+            filled: frontier.iter().copied().collect(),
+            frontier,
+            leaked: HashSet::new(),
+            minute: 0,
+        })
+    }
+
+    pub fn get_minute(&self) -> u32 {
+        self.minute
+    }
+
+    pub fn get_frontier(&self) -> &[Coord] {
+        &self.frontier
+    }
+
+    pub fn num_filled(&self) -> usize {
+        self.filled.len()
+    }
+
+    pub fn is_filled(&self, pos: Coord) -> bool {
+        self.filled.contains(&pos)
+    }
+
+    fn is_open(&self, pos: Coord) -> bool {
+        is_open_tile(self.ship_map, pos)
+    }
+
+    /// Open tiles the oxygen never got to, in reading order. Once the
+    /// simulation has finished these are the tiles cut off by leaks.
+    pub fn get_unfilled(&self) -> Vec<Coord> {
+        let mut unfilled = self.ship_map.tiles.iter().map(|(pos, _)| pos)
+            .filter(|pos| self.is_open(*pos))
+            .filter(|pos| !self.filled.contains(pos) && !self.leaks.contains(pos))
+            .collect::<Vec<Coord>>();
+        sort_reading_order(&mut unfilled);
+        unfilled
+    }
+
+    /// Advances one minute. Returns `None` once the oxygen has stopped
+    /// spreading.
+    pub fn step(&mut self) -> Option<DiffusionStep> {
+        let mut frontier = vec![];
+        let mut leaking = vec![];
+
+        for pos in std::mem::take(&mut self.frontier) {
+            for dir in SEARCH_DIRS.iter() {
+                let next = dir.move_coord(pos);
+                if !self.is_open(next) || self.filled.contains(&next) {
+                    continue;
+                }
+                if self.leaks.contains(&next) {
+                    if self.leaked.insert(next) {
+                        leaking.push(next);
+                    }
+                    continue;
+                }
+                self.filled.insert(next);
+                frontier.push(next);
+            }
+        }
+
+        if frontier.is_empty() && leaking.is_empty() {
+            return None;
+        }
+
+        sort_reading_order(&mut frontier);
+        sort_reading_order(&mut leaking);
+        self.minute += 1;
+        self.frontier = frontier.clone();
+
+        Some(DiffusionStep {
+            minute: self.minute,
+            frontier,
+            leaking,
+            num_filled: self.filled.len(),
+        })
+    }
+
+    /// Runs to the end and returns every minute's step.
+    pub fn run(&mut self) -> Vec<DiffusionStep> {
+        std::iter::from_fn(|| self.step()).collect()
+    }
+
+    /// The minute the last tile filled. Leaks reached after that don't
+    /// count, since nothing else changes.
+    pub fn get_fill_time(steps: &[DiffusionStep]) -> u32 {
+        steps.iter().rev()
+            .find(|step| !step.frontier.is_empty())
+            .map(|step| step.minute)
+            .unwrap_or(0)
+    }
+
+    pub fn render(&self) -> String {
+        let frontier = self.frontier.iter().collect::<HashSet<&Coord>>();
+        render_map(self.ship_map, |pos, c| {
+            if self.sources.contains(&pos) {
+                SOURCE_CHAR
+            } else if self.leaks.contains(&pos) {
+                LEAK_CHAR
+            } else if frontier.contains(&pos) {
+                FRONTIER_CHAR
+            } else if self.filled.contains(&pos) {
+                FILLED_CHAR
+            } else if c == get_tile_char(Tile::Oxygen) {
+                // the oxygen system itself only shows once oxygen gets there
+                get_tile_char(Tile::Space)
+            } else {
+                c
+            }
+        })
+    }
+
+    /// The current state as a plain (P2) PGM, one pixel per tile.
+    pub fn render_pgm(&self) -> String {
+        let (top_left, bottom_right) = self.ship_map.bounds();
+        let width = bottom_right.x - top_left.x + 1;
+        let height = bottom_right.y - top_left.y + 1;
+        let mut txt = format!("P2\n{} {}\n255\n", width, height);
+        self.render().lines().for_each(|line| {
+            let row = line.chars()
+                .map(|c| match c {
+                    '#' | '.' => "0",
+                    FILLED_CHAR => "160",
+                    FRONTIER_CHAR => "255",
+                    SOURCE_CHAR => "255",
+                    LEAK_CHAR => "40",
+                    _ => "90",
+                })
+                .collect::<Vec<&str>>();
+            txt.push_str(&row.join(" "));
+            txt.push('\n');
+        });
+        txt
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FrameFormat { Text, Pgm }
+
+/// Runs the simulation to the end, writing every minute (including minute
+/// 0) to `dir` as `frame_NNNN.txt` or `frame_NNNN.pgm`. Returns the steps.
+pub fn export_frames(sim: &mut OxygenSim, dir: &str, format: FrameFormat)
+-> Vec<DiffusionStep> {
+    let err_msg = format!("Something went wrong creating the frame dir: {}", dir);
+    fs::create_dir_all(dir).expect(&err_msg);
+
+    let write_frame = |sim: &OxygenSim| {
+        let (ext, contents) = match format {
+            FrameFormat::Text => ("txt", sim.render()),
+            FrameFormat::Pgm => ("pgm", sim.render_pgm()),
+        };
+        let filename = format!("{}/frame_{:04}.{}", dir, sim.get_minute(), ext);
+        let err_msg = format!("Something went wrong writing the frame: {}", filename);
+        fs::write(&filename, contents).expect(&err_msg);
+    };
+
+    write_frame(sim);
+    let mut steps = vec![];
+    while let Some(step) = sim.step() {
+        write_frame(sim);
+        steps.push(step);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_map(rows: &[&str]) -> ShipMap {
+        let mut ship_map = ShipMap::new();
+        rows.iter().enumerate().for_each(|(y, row)| {
+            row.chars().enumerate().for_each(|(x, c)| {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    'O' => Tile::Oxygen,
+                    '.' => Tile::Space,
+                    _ => return,
+                };
+                ship_map.update_tile(Coord { x: x as i32, y: y as i32 }, tile);
+            });
+        });
+        ship_map
+    }
+
+    // the example from the puzzle description
+    fn example_map() -> ShipMap {
+        make_map(&[
+            " ##   ",
+            "#..## ",
+            "#.#..#",
+            "#.O.# ",
+            " ###  ",
+        ])
+    }
+
+    #[test]
+    fn test_single_source() {
+        let ship_map = example_map();
+        let oxygen_pos = ship_map.get_oxygen_pos().unwrap();
+        let mut sim = OxygenSim::new(&ship_map, &[oxygen_pos], &[]).unwrap();
+        let steps = sim.run();
+
+        assert_eq!(OxygenSim::get_fill_time(&steps), 4);
+        assert_eq!(steps[0].frontier, vec![Coord { x: 1, y: 3 }, Coord { x: 3, y: 3 }]);
+        assert_eq!(steps[3].frontier, vec![Coord { x: 2, y: 1 }]);
+        assert_eq!(steps[3].num_filled, 8);
+        assert!(sim.get_unfilled().is_empty());
+        assert_eq!(
+            OxygenSim::get_fill_time(&steps),
+            find_longest_path_len(&ship_map, oxygen_pos),
+        );
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        let ship_map = example_map();
+        let sources = [Coord { x: 2, y: 3 }, Coord { x: 2, y: 1 }];
+        let mut sim = OxygenSim::new(&ship_map, &sources, &[]).unwrap();
+        let steps = sim.run();
+        assert_eq!(OxygenSim::get_fill_time(&steps), 3);
+        assert_eq!(steps[1].frontier, vec![Coord { x: 1, y: 2 }, Coord { x: 3, y: 2 }]);
+    }
+
+    #[test]
+    fn test_leak() {
+        let ship_map = example_map();
+        let leak = Coord { x: 1, y: 2 };
+        let mut sim = OxygenSim::new(&ship_map, &[Coord { x: 2, y: 3 }], &[leak]).unwrap();
+        let steps = sim.run();
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[1].leaking, vec![leak]);
+        assert_eq!(OxygenSim::get_fill_time(&steps), 3);
+        assert_eq!(sim.get_unfilled(), vec![Coord { x: 1, y: 1 }, Coord { x: 2, y: 1 }]);
+        assert!(!sim.is_filled(leak));
+    }
+
+    #[test]
+    fn test_closed_sources_and_leaks() {
+        let ship_map = example_map();
+        let open = Coord { x: 2, y: 3 };
+        assert_eq!(OxygenSim::new(&ship_map, &[Coord { x: 0, y: 1 }], &[]).err(),
+            Some("source at 0,1 isn't on an open tile (Wall)".to_string()));
+        assert_eq!(OxygenSim::new(&ship_map, &[open], &[Coord { x: 5, y: 0 }]).err(),
+            Some("leak at 5,0 isn't on an open tile (Unknown)".to_string()));
+        assert!(OxygenSim::new(&ship_map, &[open], &[Coord { x: 1, y: 2 }]).is_ok());
+    }
+
+    #[test]
+    fn test_render() {
+        let ship_map = example_map();
+        let mut sim = OxygenSim::new(&ship_map, &[Coord { x: 2, y: 3 }], &[]).unwrap();
+        sim.step();
+        sim.step();
+        assert_eq!(sim.render(), [
+            ".##...",
+            "#  ##.",
+            "#o#o #",
+            "#O@O#.",
+            ".###..",
+        ].iter().map(|row| format!("{}\n", row)).collect::<String>());
+    }
+}
@@ -1,180 +1,119 @@
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::collections::LinkedList;
-
-use aoc2019_utils::*;
+use aoc2019_utils::grid;
+use aoc2019_utils::grid_robot::*;
+use aoc2019_utils::search;
 
 use crate::day15_cpu::*;
 
-pub type Coord = point_2d::Point2d<i16>;
+pub type Coord = RobotCoord;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Tile { Space, Wall, Oxygen, Unknown }
 
+impl FromIntcode for Tile {
+    fn from_intcode(num: i64) -> Self {
+        match num {
+            0 => Self::Wall,
+            1 => Self::Space,
+            2 => Self::Oxygen,
+            _ => panic!("bad result num"),
+        }
+    }
+}
+
+impl Obstacle for Tile {
+    fn is_obstacle(&self) -> bool {
+        *self == Self::Wall
+    }
+}
+
+/// What the droid has found so far. Tiles it hasn't been to are unknown.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ShipMap {
-    pub tiles: HashMap<Coord, Tile>,
-    pub top: i16,
-    pub left: i16,
-    pub bottom: i16,
-    pub right: i16,
+    pub tiles: grid::SparseGrid<Tile>,
+}
+
+impl Default for ShipMap {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ShipMap {
-    fn new() -> Self {
-        Self {
-            tiles: HashMap::new(),
-            top: 0,
-            left: 0,
-            bottom: 0,
-            right: 0,
-        }
+    pub fn new() -> Self {
+        Self { tiles: grid::SparseGrid::new(Tile::Unknown) }
     }
 
-    fn update_tile(&mut self, coord: Coord, tile: Tile) {
-        self.tiles.insert(coord, tile);
+    pub fn update_tile(&mut self, coord: Coord, tile: Tile) {
+        self.tiles.set(coord, tile);
+    }
 
-        self.top = std::cmp::min(self.top, coord.y);
-        self.bottom = std::cmp::max(self.bottom, coord.y);
-        self.left = std::cmp::min(self.left, coord.x);
-        self.right = std::cmp::max(self.right, coord.x);
+    pub fn get_tile_at(&self, coord: Coord) -> Tile {
+        *self.tiles.get(coord)
     }
 
-    fn get_tile_at(&self, coord: Coord) -> Tile {
-        match self.tiles.get(&coord) {
-            None => Tile::Unknown,
-            Some(tile) => *tile,
-        }
+    /// The top left and bottom right corners of the explored area, both
+    /// included. An empty map is just the start.
+    pub fn bounds(&self) -> (Coord, Coord) {
+        let start = Coord { x: 0, y: 0 };
+        self.tiles.bounds().unwrap_or((start, start))
     }
 
     pub fn get_oxygen_pos(&self) -> Option<Coord> {
-        self.tiles.iter().filter(|(_, tile)| {
-            **tile == Tile::Oxygen
-        }).map(|(pos, _)| {
-            *pos
-        }).next()
+        self.tiles.iter()
+            .find(|(_, tile)| **tile == Tile::Oxygen)
+            .map(|(pos, _)| pos)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Dir { North, South, West, East }
-
-impl Dir {
-    fn to_num(self) -> i64 {
-        match self {
-            Self::North => 1,
-            Self::South => 2,
-            Self::West => 3,
-            Self::East => 4,
-        }
-    }
-}
+impl WorldModel for ShipMap {
+    type Cell = Tile;
 
-fn move_coord(coord: Coord, dir: Dir) -> Coord {
-    let mut result = coord;
-    match dir {
-        Dir::North => result.y -= 1,
-        Dir::South => result.y += 1,
-        Dir::West => result.x -= 1,
-        Dir::East => result.x += 1,
+    fn get_cell(&self, pos: Coord) -> Tile {
+        self.get_tile_at(pos)
     }
-    result
-}
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum MoveResult { HitWall, Moved, OnOxygen }
-
-impl MoveResult {
-    fn from_num(num: i64) -> Self {
-        match num {
-            0 => Self::HitWall,
-            1 => Self::Moved,
-            2 => Self::OnOxygen,
-            _ => panic!("bad result num"),
-        }
+    fn set_cell(&mut self, pos: Coord, tile: Tile) {
+        self.update_tile(pos, tile);
     }
 }
 
-pub struct Robot {
-    cpu: Cpu,
-    pos: Coord,
-}
+pub const SEARCH_DIRS: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Right, Dir::Left];
 
-impl Robot {
-    fn new(prog: &Vec<i64>) -> Self {
-        let mut cpu = Cpu::new(prog);
-        cpu.set_print_output(false);
-        Robot {
-            cpu: cpu,
-            pos: Coord { x: 0, y: 0 },
-        }
-    }
+/// Searches move to any neighbour that isn't a known wall.
+impl search::Graph for ShipMap {
+    type Node = Coord;
 
-    fn take_step(&mut self, dir: Dir) -> MoveResult {
-        self.cpu.add_input(dir.to_num());
-        self.cpu.exec_prog();
-        let output = self.cpu.pop_output().unwrap();
-        let result = MoveResult::from_num(output);
+    fn edges(&self, pos: &Coord) -> Vec<(Coord, u32)> {
+        SEARCH_DIRS.iter()
+            .map(|dir| dir.move_coord(*pos))
+            .filter(|next| self.get_tile_at(*next) != Tile::Wall)
+            .map(|next| (next, 1))
+            .collect()
+    }
+}
 
-        if result != MoveResult::HitWall {
-            self.pos = move_coord(self.pos, dir);
-        }
+pub type Robot = GridRobot<Cpu, MoveProtocol<ShipMap>>;
 
-        result
-    }
+pub fn new_robot(prog: &[i64]) -> Robot {
+    let mut cpu = Cpu::new(prog);
+    cpu.set_print_output(false);
+    let pose = Pose { pos: Coord { x: 0, y: 0 }, dir: Dir::Up };
+    GridRobot::new(cpu, MoveProtocol::default(), pose)
 }
 
 pub fn find_longest_path_len(
     ship_map: &ShipMap,
     from_pos: Coord,
 ) -> u32 {
-    struct CoordAndDist {
-        pos: Coord,
-        dist: u32,
-    }
-
-    let mut to_visit = LinkedList::new();
-    let mut visited = HashSet::new();
-    to_visit.push_back(
-        CoordAndDist {
-            pos: from_pos,
-            dist: 0,
-        }
-    );
-
-    let get_next_visits = |at, visited: &HashSet<Coord>| -> Vec<CoordAndDist> {
-        [Dir::North, Dir::South, Dir::East, Dir::West].iter()
-            .map(|dir| {
-                CoordAndDist {
-                    pos: move_coord(at, *dir),
-                    dist: 0,
-                }
-            })
-            .filter(|coord_and_dist| {
-                !visited.contains(&coord_and_dist.pos)
-                    && ship_map.get_tile_at(coord_and_dist.pos) != Tile::Wall
-            })
-            .collect()
-    };
-
-    let mut max_dist = 0;
-
-    loop {
-        if to_visit.is_empty() {
-            break;
-        }
-
-        let at = to_visit.pop_front().unwrap();
-        max_dist = at.dist;
-
-        visited.insert(at.pos);
-
-        get_next_visits(at.pos, &visited).into_iter().for_each(|mut visit| {
-            visit.dist = at.dist + 1;
-            to_visit.push_back(visit);
-        });
-    }
+    search::flood_fill(ship_map, from_pos).get_max_cost()
+}
 
-    max_dist
+pub fn find_shortest_path(
+    ship_map: &ShipMap,
+    from_pos: Coord,
+    to_pos: Coord,
+) -> Option<Vec<Coord>> {
+    search::bfs(ship_map, from_pos, |pos| *pos == to_pos).map(|found| found.path)
 }
 
 pub fn find_shortest_path_len(
@@ -182,138 +121,68 @@ pub fn find_shortest_path_len(
     from_pos: Coord,
     to_pos: Coord,
 ) -> Option<u32> {
-    struct CoordAndDist {
-        pos: Coord,
-        dist: u32,
-    }
-
-    let mut to_visit = LinkedList::new();
-    let mut visited = HashSet::new();
-    to_visit.push_back(
-        CoordAndDist {
-            pos: from_pos,
-            dist: 0,
-        }
-    );
-
-    let get_next_visits = |at, visited: &HashSet<Coord>| -> Vec<CoordAndDist> {
-        [Dir::North, Dir::South, Dir::East, Dir::West].iter()
-            .map(|dir| {
-                CoordAndDist {
-                    pos: move_coord(at, *dir),
-                    dist: 0,
-                }
-            })
-            .filter(|coord_and_dist| {
-                !visited.contains(&coord_and_dist.pos)
-                    && ship_map.get_tile_at(coord_and_dist.pos) != Tile::Wall
-            })
-            .collect()
-    };
-
-    loop {
-        if to_visit.is_empty() {
-            break;
-        }
-
-        let at = to_visit.pop_front().unwrap();
-        if at.pos == to_pos {
-            return Some(at.dist);
-        }
-
-        visited.insert(at.pos);
-
-        get_next_visits(at.pos, &visited).into_iter().for_each(|mut visit| {
-            visit.dist = at.dist + 1;
-            to_visit.push_back(visit);
-        });
-    }
-
-    None
+    search::bfs(ship_map, from_pos, |pos| *pos == to_pos).map(|found| found.cost)
 }
 
 fn find_closest_unknown_tile_dir(ship_map: &ShipMap, start_pos: Coord)
 -> Option<Dir> {
-    struct CoordAndDir {
-        pos: Coord,
-        dir: Dir,
-    }
-
-    let mut to_visit = LinkedList::new();
-    let mut visited = HashSet::new();
-    visited.insert(start_pos);
-
-    let get_next_visits = |at, visited: &HashSet<Coord>| -> Vec<CoordAndDir> {
-        [Dir::North, Dir::South, Dir::East, Dir::West].iter()
-            .map(|dir| {
-                CoordAndDir {
-                    pos: move_coord(at, *dir),
-                    dir: *dir,
-                }
-            })
-            .filter(|coord_and_dir| {
-                !visited.contains(&coord_and_dir.pos)
-                    && ship_map.get_tile_at(coord_and_dir.pos) != Tile::Wall
-            })
-            .collect()
-    };
-
-    get_next_visits(start_pos, &visited).into_iter().for_each(|visit| {
-        to_visit.push_back(visit);
-    });
-
-    loop {
-        if to_visit.is_empty() {
-            break;
-        }
-
-        let at = to_visit.pop_front().unwrap();
-        if ship_map.get_tile_at(at.pos) == Tile::Unknown {
-            return Some(at.dir);
-        }
-
-        visited.insert(at.pos);
-
-        get_next_visits(at.pos, &visited).into_iter().for_each(|mut visit| {
-            visit.dir = at.dir;
-            to_visit.push_back(visit);
-        });
-    }
+    let found = search::bfs(ship_map, start_pos, |pos| {
+        ship_map.get_tile_at(*pos) == Tile::Unknown
+    })?;
+    SEARCH_DIRS.iter().copied().find(|dir| dir.move_coord(start_pos) == found.path[1])
+}
 
-    None
+pub fn create_map(prog: &[i64]) -> (ShipMap, Coord) {
+    let (ship_map, start_pos, _) = walk_map(prog);
+    (ship_map, start_pos)
 }
 
-pub fn create_map(prog: &Vec<i64>) -> (ShipMap, Coord) {
-    let mut robot = Robot::new(prog);
-    let start_pos = robot.pos;
+/// Maps the ship with a single droid, always heading for the nearest unknown
+/// tile. Also returns the number of moves the droid was sent.
+pub fn walk_map(prog: &[i64]) -> (ShipMap, Coord, usize) {
+    let mut robot = new_robot(prog);
+    let start_pos = robot.get_pos();
 
     let mut ship_map = ShipMap::new();
     ship_map.update_tile(start_pos, Tile::Space);
 
-    while let Some(dir) = find_closest_unknown_tile_dir(&ship_map, robot.pos) {
-        let (tile, pos) = match robot.take_step(dir) {
-            MoveResult::HitWall => (Tile::Wall, move_coord(robot.pos, dir)),
-            MoveResult::Moved => (Tile::Space, robot.pos),
-            MoveResult::OnOxygen => (Tile::Oxygen, robot.pos),
-        };
-        ship_map.update_tile(pos, tile);
+    let mut num_moves = 0;
+    while let Some(dir) = find_closest_unknown_tile_dir(&ship_map, robot.get_pos()) {
+        robot.step(&mut ship_map, dir).unwrap_or_else(|err| panic!("bad droid program: {}", err));
+        num_moves += 1;
     }
 
-    (ship_map, start_pos)
+    (ship_map, start_pos, num_moves)
 }
 
-pub fn print_map(ship_map: &ShipMap) {
-    println!("");
-    (ship_map.top..=ship_map.bottom).for_each(|y|{
-        (ship_map.left..=ship_map.right).for_each(|x|{
-            let c = match ship_map.get_tile_at(Coord { x: x, y: y }) {
-                Tile::Space => ' ',
-                Tile::Wall => '#',
-                Tile::Oxygen => 'O',
-                Tile::Unknown => '.',
-            };
-            print!("{}", c);
+pub fn get_tile_char(tile: Tile) -> char {
+    match tile {
+        Tile::Space => ' ',
+        Tile::Wall => '#',
+        Tile::Oxygen => 'O',
+        Tile::Unknown => '.',
+    }
+}
+
+/// Draws the map row by row, letting `get_char` override the character for
+/// any tile (it gets the tile's coordinate and its default character).
+pub fn render_map<F>(ship_map: &ShipMap, get_char: F) -> String
+where
+    F: Fn(Coord, char) -> char,
+{
+    let (top_left, bottom_right) = ship_map.bounds();
+    let mut txt = String::new();
+    (top_left.y..=bottom_right.y).for_each(|y| {
+        (top_left.x..=bottom_right.x).for_each(|x| {
+            let coord = Coord { x, y };
+            txt.push(get_char(coord, get_tile_char(ship_map.get_tile_at(coord))));
         });
-        println!("");
+        txt.push('\n');
     });
+    txt
+}
+
+pub fn print_map(ship_map: &ShipMap) {
+    println!();
+    print!("{}", render_map(ship_map, |_, c| c));
 }
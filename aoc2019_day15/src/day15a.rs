@@ -1,5 +1,5 @@
-pub mod day15_cpu;
-pub mod day15_utils;
+use aoc2019_day15::day15_cpu;
+use aoc2019_day15::day15_utils;
 
 use day15_cpu::*;
 use day15_utils::*;
@@ -10,8 +10,9 @@ fn main() {
     println!("prog len: {}", prog.len());
     let (ship_map, start_pos) = create_map(&prog);
     let oxygen_pos = ship_map.get_oxygen_pos();
+    let (top_left, bottom_right) = ship_map.bounds();
     println!("ship map bounds: top: {}, left: {}, bottom: {}, right: {}",
-        ship_map.top, ship_map.left, ship_map.bottom, ship_map.right);
+        top_left.y, top_left.x, bottom_right.y, bottom_right.x);
     println!("  with {} tiles", ship_map.tiles.len());
     print_map(&ship_map);
     println!("");
@@ -1,28 +1,131 @@
-pub mod day15_cpu;
-pub mod day15_utils;
+use aoc2019_day15::day15_cpu;
+use aoc2019_day15::day15_oxygen;
+use aoc2019_day15::day15_utils;
+
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
 
 use day15_cpu::*;
+use day15_oxygen::*;
 use day15_utils::*;
 
+const DEFAULT_FRAME_MS: u64 = 50;
+
+struct Options {
+    sources: Vec<Coord>,
+    leaks: Vec<Coord>,
+    animate: bool,
+    frame_delay: Duration,
+    frames_dir: Option<String>,
+    frame_format: FrameFormat,
+}
+
+fn parse_coord(txt: &str) -> Coord {
+    let nums = txt.split(',')
+        .map(|num| i32::from_str(num.trim()).expect("bad coord"))
+        .collect::<Vec<i32>>();
+    if nums.len() != 2 {
+        panic!("bad coord: {} (expected x,y)", txt);
+    }
+    Coord { x: nums[0], y: nums[1] }
+}
+
+fn get_options() -> Options {
+    let mut options = Options {
+        sources: vec![],
+        leaks: vec![],
+        animate: false,
+        frame_delay: Duration::from_millis(DEFAULT_FRAME_MS),
+        frames_dir: None,
+        frame_format: FrameFormat::Text,
+    };
+
+    let usage = "--source x,y, --leak x,y, --animate, --frame-ms <ms>, --frames <dir>, --pgm";
+    aoc2019_utils::parse_args(usage, |arg, next_arg| {
+        match arg {
+            "--source" => options.sources.push(parse_coord(&next_arg())),
+            "--leak" => options.leaks.push(parse_coord(&next_arg())),
+            "--animate" => options.animate = true,
+            "--frame-ms" => {
+                let ms = u64::from_str(&next_arg()).expect("bad --frame-ms value");
+                options.frame_delay = Duration::from_millis(ms);
+            },
+            "--frames" => options.frames_dir = Some(next_arg()),
+            "--pgm" => options.frame_format = FrameFormat::Pgm,
+            _ => return false,
+        }
+        true
+    });
+
+    options
+}
+
+fn animate(sim: &mut OxygenSim, frame_delay: Duration) -> Vec<DiffusionStep> {
+    let draw = |sim: &OxygenSim| {
+        print!("\x1b[H{}minute {}, {} tiles filled\x1b[J",
+            sim.render(), sim.get_minute(), sim.num_filled());
+        std::io::stdout().flush().unwrap();
+        std::thread::sleep(frame_delay);
+    };
+
+    print!("\x1b[2J\x1b[?25l");
+    draw(sim);
+    let mut steps = vec![];
+    while let Some(step) = sim.step() {
+        draw(sim);
+        steps.push(step);
+    }
+    println!("\x1b[?25h");
+    steps
+}
+
 fn main() {
+    let mut options = get_options();
     let input = aoc2019_utils::get_input("inputs/day15.txt");
     let prog = parse_prog(&input);
     println!("prog len: {}", prog.len());
     let (ship_map, _) = create_map(&prog);
     let oxygen_pos = ship_map.get_oxygen_pos();
+    let (top_left, bottom_right) = ship_map.bounds();
     println!("ship map bounds: top: {}, left: {}, bottom: {}, right: {}",
-        ship_map.top, ship_map.left, ship_map.bottom, ship_map.right);
+        top_left.y, top_left.x, bottom_right.y, bottom_right.x);
     println!("  with {} tiles", ship_map.tiles.len());
     print_map(&ship_map);
-    println!("");
-    if !oxygen_pos.is_some() {
+    println!();
+    if oxygen_pos.is_none() && options.sources.is_empty() {
         println!("no oxygen found");
         return;
     }
 
-    let oxygen_pos = oxygen_pos.unwrap();
-    println!("oxygen at: {}, {}", oxygen_pos.x, oxygen_pos.y);
+    if let Some(oxygen_pos) = oxygen_pos {
+        println!("oxygen at: {}, {}", oxygen_pos.x, oxygen_pos.y);
+        if options.sources.is_empty() {
+            options.sources.push(oxygen_pos);
+        }
+    }
+
+    let mut sim = match OxygenSim::new(&ship_map, &options.sources, &options.leaks) {
+        Ok(sim) => sim,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        },
+    };
+    let steps = if let Some(dir) = &options.frames_dir {
+        let steps = export_frames(&mut sim, dir, options.frame_format);
+        println!("wrote {} frames to {}", steps.len() + 1, dir);
+        steps
+    } else if options.animate {
+        animate(&mut sim, options.frame_delay)
+    } else {
+        sim.run()
+    };
 
-    let max_dist = find_longest_path_len(&ship_map, oxygen_pos);
-    println!("fill time: {} min", max_dist);
+    let leaks_reached = steps.iter().map(|step| step.leaking.len()).sum::<usize>();
+    if !options.leaks.is_empty() {
+        println!("leaks reached: {} of {}", leaks_reached, options.leaks.len());
+        println!("tiles cut off: {}", sim.get_unfilled().len());
+    }
+    println!("fill time: {} min", OxygenSim::get_fill_time(&steps));
 }
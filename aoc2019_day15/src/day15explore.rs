@@ -0,0 +1,38 @@
+use aoc2019_day15::day15_cpu;
+use aoc2019_day15::day15_explore;
+
+use std::time::Instant;
+
+use day15_cpu::*;
+use day15_explore::*;
+
+fn main() {
+    let input = aoc2019_utils::get_input("inputs/day15.txt");
+    let prog = parse_prog(&input);
+
+    println!("{:<10} {:>8} {:>8} {:>10} {:>12}",
+        "explorer", "tiles", "moves", "oxygen", "time");
+
+    let results = get_explorers().iter().map(|explorer| {
+        let start_time = Instant::now();
+        let result = explorer.explore(&prog);
+        let elapsed = start_time.elapsed();
+
+        let oxygen_dist = result.ship_map.get_oxygen_pos()
+            .and_then(|pos| result.path_tree.get_cost(&pos));
+        let oxygen_dist = match oxygen_dist {
+            None => "none".to_string(),
+            Some(dist) => dist.to_string(),
+        };
+        println!("{:<10} {:>8} {:>8} {:>10} {:>12?}",
+            explorer.get_name(), result.ship_map.tiles.len(), result.num_moves,
+            oxygen_dist, elapsed);
+
+        result
+    }).collect::<Vec<ExploreResult>>();
+
+    let all_match = results.windows(2).all(|pair| {
+        pair[0].ship_map == pair[1].ship_map
+    });
+    println!("maps match: {}", all_match);
+}
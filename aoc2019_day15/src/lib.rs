@@ -0,0 +1,42 @@
+pub mod day15_cpu;
+pub mod day15_explore;
+pub mod day15_oxygen;
+pub mod day15_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day15_cpu::*;
+use day15_oxygen::*;
+use day15_utils::*;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        15
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        let (ship_map, start_pos) = create_map(prog);
+        ship_map.get_oxygen_pos()
+            .and_then(|oxygen_pos| find_shortest_path_len(&ship_map, start_pos, oxygen_pos))
+            .into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        let (ship_map, _) = create_map(prog);
+        ship_map.get_oxygen_pos()
+            .map(|oxygen_pos| {
+                let mut sim = OxygenSim::new(&ship_map, &[oxygen_pos], &[])
+                    .expect("the oxygen system is on an open tile");
+                OxygenSim::get_fill_time(&sim.run())
+            })
+            .into()
+    }
+}
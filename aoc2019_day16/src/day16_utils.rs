@@ -154,6 +154,42 @@ pub fn calc_next_phase_v3(input: &Vec<i32>) -> Vec<i32> {
     output
 }
 
+pub fn get_message_offset(nums: &[i32]) -> usize {
+    nums[0..7].iter().fold(0, |acc, n| acc * 10 + n) as usize
+}
+
+// In the back half of the signal every multiplier at or after a digit's own
+// position is 1, so each new digit is just the sum of the digits after it.
+// That makes a phase one pass of running sums, which is all part B needs when
+// the message offset lands there.
+pub fn decode_message(nums: &Vec<i32>, num_repeats: usize, num_phases: usize)
+    -> String
+{
+    let msg_offset = get_message_offset(nums);
+    let full_len = nums.len() * num_repeats;
+
+    if msg_offset * 2 < full_len {
+        let mut num_list = repeat_input(nums, num_repeats);
+        for _ in 0..num_phases {
+            num_list = calc_next_phase_v3(&num_list);
+        }
+        return get_output_text(&num_list, msg_offset, 8);
+    }
+
+    let mut tail = (msg_offset..full_len)
+        .map(|idx| nums[idx % nums.len()])
+        .collect::<Vec<i32>>();
+    for _ in 0..num_phases {
+        let mut sum = 0;
+        for n in tail.iter_mut().rev() {
+            sum = (sum + *n) % 10;
+            *n = sum;
+        }
+    }
+
+    get_output_text(&tail, 0, 8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,4 +360,13 @@ mod tests {
         let result = calc_digit_from_ranges(&ranges);
         assert_eq!(result, 6);
     }
+
+    #[test]
+    fn test_decode_message() {
+        let input = parse_input("03036732577212944063491565474664");
+        assert_eq!(decode_message(&input, 10000, 100), "84462026");
+
+        let input = parse_input("02935109699940807407585447034323");
+        assert_eq!(decode_message(&input, 10000, 100), "78725270");
+    }
 }
@@ -1,4 +1,4 @@
-pub mod day16_utils;
+use aoc2019_day16::day16_utils;
 
 use day16_utils::*;
 
@@ -1,4 +1,4 @@
-pub mod day16_utils;
+use aoc2019_day16::day16_utils;
 
 use aoc2019_utils::*;
 use day16_utils::*;
@@ -12,8 +12,7 @@ fn main() {
     let mut num_list = repeat_input(&num_list, INPUT_REPEAT_COUNT);
     println!("generated input of len: {}", num_list.len());
 
-    let msg_offset = num_list[0..7].iter()
-        .fold(0, |acc, n| acc * 10 + n) as usize;
+    let msg_offset = get_message_offset(&num_list);
     println!("message offset: {}", msg_offset);
 
     let start_time = Instant::now();
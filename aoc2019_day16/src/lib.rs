@@ -0,0 +1,34 @@
+pub mod day16_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day16_utils::*;
+
+const PHASE_COUNT: usize = 100;
+const INPUT_REPEAT_COUNT: usize = 10000;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Vec<i32>;
+
+    fn get_day(&self) -> u32 {
+        16
+    }
+
+    fn parse(&self, input: &str) -> Vec<i32> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, nums: &Vec<i32>) -> Answer {
+        let mut num_list = nums.clone();
+        for _ in 0..PHASE_COUNT {
+            num_list = calc_next_phase_v3(&num_list);
+        }
+        get_output_text(&num_list, 0, 8).into()
+    }
+
+    fn solve_b(&self, nums: &Vec<i32>) -> Answer {
+        decode_message(nums, INPUT_REPEAT_COUNT, PHASE_COUNT).into()
+    }
+}
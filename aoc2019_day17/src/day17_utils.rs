@@ -1,30 +1,27 @@
 use aoc2019_utils::*;
 
-// use crate::day17_cpu::*;
+use crate::day17_cpu::*;
 
-pub type Coord = point_2d::Point2d<u8>;
+use point_2d::Direction as Dir;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Dir { Up, Down, Left, Right }
+pub type Coord = point_2d::Point2d<u8>;
 
-impl Dir {
-    fn from_char(c: char) -> Self {
-        match c {
-            '^' => Self::Up,
-            'v' => Self::Down,
-            '>' => Self::Left,
-            '<' => Self::Right,
-            _ => panic!("bad char for Dir: {}", c),
-        }
+fn dir_from_char(c: char) -> Dir {
+    match c {
+        '^' => Dir::Up,
+        'v' => Dir::Down,
+        '<' => Dir::Left,
+        '>' => Dir::Right,
+        _ => panic!("bad char for Dir: {}", c),
     }
+}
 
-    fn to_char(self) -> char {
-        match self {
-            Self::Up => '^',
-            Self::Down => 'v',
-            Self::Left => '>',
-            Self::Right => '<',
-        }
+fn dir_to_char(dir: Dir) -> char {
+    match dir {
+        Dir::Up => '^',
+        Dir::Down => 'v',
+        Dir::Left => '<',
+        Dir::Right => '>',
     }
 }
 
@@ -39,99 +36,94 @@ pub enum Tile { Space, Scaffold }
 
 impl Tile {
     pub fn from_char(c: char) -> Self {
+        Self::try_from_char(c).unwrap_or_else(|| panic!("bad tile char: '{}'", c))
+    }
+
+    fn try_from_char(c: char) -> Option<Self> {
         match c {
-            '#' | '^' | 'v' | '>' | '<' => Self::Scaffold,
-            '.' => Self::Space,
-            _ => panic!("bad tile char: '{}'", c),
+            '#' | '^' | 'v' | '>' | '<' => Some(Self::Scaffold),
+            '.' => Some(Self::Space),
+            _ => None,
         }
     }
-}
 
-pub type ScafMap = Vec<Vec<Tile>>;
-
-pub fn parse_map_from_robot(to_parse: &Vec<i64>) -> (ScafMap, Pose) {
-    let width = to_parse.iter().position(|c| *c == ('\n' as i64)).unwrap();
-    let height = to_parse.iter()
-        .fold(0, |acc, c| if *c == ('\n' as i64) { acc + 1 } else { acc }) - 1;
-    println!("width/height: {} / {}", width, height);
-
-    let mut new_scaf_map = vec![vec![Tile::Space; height]; width];
-    let mut robot_pose = Pose {
-        pos: Coord { x: 0, y: 0 },
-        dir: Dir::Up,
-    };
-
-    for y in 0..height {
-        for x in 0..width {
-            let idx = (y * (width + 1)) + x;
-            let c = to_parse[idx] as u8 as char;
-            new_scaf_map[x][y] = Tile::from_char(c);
-            match c {
-                '^' | 'v' | '>' | '<' => {
-                    robot_pose = Pose {
-                        pos: Coord { x: x as u8, y: y as u8 },
-                        dir: Dir::from_char(c as char),
-                    };
-                },
-                _ => {},
-            };
+    fn to_char(self) -> char {
+        match self {
+            Self::Space => '.',
+            Self::Scaffold => '#',
         }
     }
+}
+
+pub type ScafMap = grid::Grid<Tile>;
+
+pub fn parse_map_from_robot(to_parse: &[i64]) -> (ScafMap, Pose) {
+    let txt = to_parse.iter().map(|c| *c as u8 as char).collect::<String>();
+    let scaf_map = ScafMap::parse(txt.trim_end(), Tile::try_from_char)
+        .expect("the camera output isn't a map");
+
+    let robot_pose = txt.trim_end().lines().enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| (x, y, c)))
+        .find(|(_, _, c)| matches!(c, '^' | 'v' | '>' | '<'))
+        .map(|(x, y, c)| Pose {
+            pos: Coord { x: x as u8, y: y as u8 },
+            dir: dir_from_char(c),
+        })
+        .unwrap_or(Pose { pos: Coord { x: 0, y: 0 }, dir: Dir::Up });
 
-    (new_scaf_map, robot_pose)
+    (scaf_map, robot_pose)
 }
 
 pub fn print_map(scaf_map: &ScafMap, pose: &Pose) {
-    let width = scaf_map.len();
-    let height = scaf_map[0].len();
-
-    for y in 0..height {
-        for x in 0..width {
-            if pose.pos == (Coord { x: x as u8, y: y as u8 }) {
-                print!("{}", pose.dir.to_char());
-            } else {
-                let c = match scaf_map[x][y] {
-                    Tile::Space => '.',
-                    Tile::Scaffold => '#',
-                };
-                print!("{}", c);
-            }
-        }
-        println!("");
-    }
+    let mut txt = scaf_map.render(|tile| tile.to_char());
+    let robot_idx = pose.pos.y as usize * (scaf_map.width() + 1) + pose.pos.x as usize;
+    txt.replace_range(robot_idx..(robot_idx + 1), &dir_to_char(pose.dir).to_string());
+    print!("{}", txt);
 }
 
 pub fn ascii_to_vec(s: &str) -> Vec<i64> {
     s.bytes().map(|c| c as i64).collect()
 }
 
-pub fn get_alignment_param(scaf_map: &ScafMap) -> u32 {
-    let width = scaf_map.len();
-    let height = scaf_map[0].len();
-
-    let mut alignment_param = 0;
-    for x in 1..(width - 1) {
-        for y in 1..(height - 1) {
-            let tile = scaf_map[x][y];
-            if tile == Tile::Space {
-                continue;
-            }
-
-            let is_intersection =
-                scaf_map[x - 1][y] != Tile::Space
-                && scaf_map[x + 1][y] != Tile::Space
-                && scaf_map[x][y - 1] != Tile::Space
-                && scaf_map[x][y + 1] != Tile::Space
-                && scaf_map[x - 1][y - 1] == Tile::Space
-                && scaf_map[x + 1][y - 1] == Tile::Space
-                && scaf_map[x - 1][y + 1] == Tile::Space
-                && scaf_map[x + 1][y + 1] == Tile::Space;
-
-            if is_intersection {
-                alignment_param += x * y;
-            }
-        }
+pub fn get_scaffold_map(prog: &Vec<i64>) -> (ScafMap, Pose) {
+    let mut cpu = Cpu::new(prog);
+    cpu.set_print_output(false);
+    cpu.exec_prog();
+    parse_map_from_robot(&cpu.get_output())
+}
+
+// These were found by inspection.
+const FUNC_A: &str = "L,12,L,10,R,8,L,12\n";
+const FUNC_B: &str = "R,8,R,10,R,12\n";
+const FUNC_C: &str = "L,10,R,12,R,8\n";
+const FUNC_MAIN: &str = "A,B,A,B,C,C,B,A,B,C\n";
+const VIEW_FEED: &str = "n\n";
+
+pub fn get_dust_amount(prog: &Vec<i64>) -> i64 {
+    let mut cpu = Cpu::new(prog);
+    cpu.set_mem_at(0, 2);
+    cpu.set_print_output(false);
+    for cmd in &[FUNC_MAIN, FUNC_A, FUNC_B, FUNC_C, VIEW_FEED] {
+        cpu.add_input_from_slice(&ascii_to_vec(cmd)[..]);
     }
+    cpu.exec_prog();
 
-    alignment_param as u32
+    *cpu.get_output().last().unwrap()
+}
+
+pub fn get_alignment_param(scaf_map: &ScafMap) -> u32 {
+    scaf_map.iter()
+        .filter(|(_, tile)| **tile == Tile::Scaffold)
+        .filter(|(coord, _)| {
+            // an intersection is a + with nothing on the diagonals
+            let num_scaffolds = scaf_map.neighbours8(*coord)
+                .filter(|next| scaf_map[*next] == Tile::Scaffold)
+                .count();
+            let num_orthogonal = scaf_map.neighbours4(*coord)
+                .filter(|next| scaf_map[*next] == Tile::Scaffold)
+                .count();
+            num_orthogonal == 4 && num_scaffolds == 4
+        })
+        .map(|(coord, _)| (coord.x * coord.y) as u32)
+        .sum()
 }
@@ -0,0 +1,30 @@
+pub mod day17_cpu;
+pub mod day17_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day17_cpu::*;
+use day17_utils::*;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        17
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        let (scaf_map, _) = get_scaffold_map(prog);
+        get_alignment_param(&scaf_map).into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        get_dust_amount(prog).into()
+    }
+}
@@ -1,6 +1,4 @@
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::collections::LinkedList;
 
 use aoc2019_utils::*;
 
@@ -47,21 +45,9 @@ fn get_num_keys(keys: KeySet) -> u32 {
 }
 
 fn get_reachable_coords(pos: Coord, width: usize, height: usize) -> Vec<Coord> {
-    let mut coords = Vec::with_capacity(4);
-    if pos.y > 0 {
-        coords.push(Coord { x: pos.x, y: pos.y - 1 });
-    }
-    if pos.y < height as u8 - 1 {
-        coords.push(Coord { x: pos.x, y: pos.y + 1 });
-    }
-    if pos.x > 0 {
-        coords.push(Coord { x: pos.x - 1, y: pos.y });
-    }
-    if pos.x < width as u8 - 1 {
-        coords.push(Coord { x: pos.x + 1, y: pos.y });
-    }
-
-    coords
+    pos.neighbours4()
+        .filter(|coord| (coord.x as usize) < width && (coord.y as usize) < height)
+        .collect()
 }
 
 pub fn print_vault(vault: &Vault) {
@@ -148,57 +134,40 @@ fn is_wall(cell: char) -> bool {
     cell == WALL
 }
 
-fn calc_map_stats_single(vault: &Vault, start: Coord)
--> (KeySet, Vec<Coord>, Vec<u32>) {
-
-    struct Visitation { pos: Coord, passed_doors: KeySet }
-
+fn vault_graph(vault: &Vault) -> impl search::Graph<Node = Coord> + '_ {
     let width = vault.len();
     let height = vault[0].len();
 
-    let mut history = HashSet::new();
-    let mut to_visit = LinkedList::new();
+    search::unit_graph_from_fn(move |pos: &Coord| {
+        get_reachable_coords(*pos, width, height).into_iter()
+            .filter(|coord| !is_wall(vault_cell_at(vault, *coord)))
+            .collect()
+    })
+}
+
+fn calc_map_stats_single(vault: &Vault, start: Coord)
+-> (KeySet, Vec<Coord>, Vec<u32>) {
 
     let mut valid_keys = 0;
     let mut key_coords = vec![ZERO_COORD; NUM_LETTERS];
     let mut needed_key_map = vec![0; NUM_LETTERS];
 
-    to_visit.push_back(Visitation { pos: start, passed_doors: 0 });
-    while !to_visit.is_empty() {
-
-        let visitation = to_visit.pop_front().unwrap();
-        history.insert(visitation.pos.clone());
-
-        let c = vault_cell_at(&vault, visitation.pos);
-
-        let passed_doors = if is_door(c) {
-            visitation.passed_doors | lock_letter_to_bit(c)
-        } else {
-            visitation.passed_doors
-        };
-
-        if is_key(c) {
-            let key_idx = (c as u8 - b'a') as usize;
-            valid_keys |= key_letter_to_bit(c);
-            key_coords[key_idx] = visitation.pos;
-            needed_key_map[key_idx] = passed_doors;
+    let tree = search::flood_fill(&vault_graph(vault), start);
+    for (pos, _) in tree.iter() {
+        let c = vault_cell_at(vault, *pos);
+        if !is_key(c) {
+            continue;
         }
 
-        let reachables = get_reachable_coords(visitation.pos, width, height);
-        let next_visits = reachables.iter()
-            .filter(|coord| {
-                !is_wall(vault_cell_at(&vault, **coord))
-                    && !history.contains(coord)
-            });
-
-        for coord in next_visits {
-            to_visit.push_back(
-                Visitation {
-                    pos: *coord,
-                    passed_doors: passed_doors,
-                }
-            );
-        }
+        let passed_doors = tree.get_path_to(pos).unwrap().into_iter()
+            .map(|coord| vault_cell_at(vault, coord))
+            .filter(|c| is_door(*c))
+            .fold(0, |doors, c| doors | lock_letter_to_bit(c));
+
+        let key_idx = (c as u8 - b'a') as usize;
+        valid_keys |= key_letter_to_bit(c);
+        key_coords[key_idx] = *pos;
+        needed_key_map[key_idx] = passed_doors;
     }
 
     (valid_keys, key_coords, needed_key_map)
@@ -240,43 +209,7 @@ fn calc_map_stats(vault: &Vault, starts: &Vec<Coord>)
 }
 
 fn calc_dist_between(vault: &Vault, p1: Coord, p2: Coord) -> Option<u32> {
-
-    struct Visitation { pos: Coord, dist: u32 }
-
-    let width = vault.len();
-    let height = vault[0].len();
-
-    let mut history = HashSet::new();
-    let mut to_visit = LinkedList::new();
-
-    to_visit.push_back(Visitation { pos: p1, dist: 0 });
-    while !to_visit.is_empty() {
-
-        let visitation = to_visit.pop_front().unwrap();
-        history.insert(visitation.pos.clone());
-
-        if visitation.pos == p2 {
-            return Some(visitation.dist);
-        }
-
-        let reachables = get_reachable_coords(visitation.pos, width, height);
-        let next_visits = reachables.iter()
-            .filter(|coord| {
-                !is_wall(vault_cell_at(&vault, **coord))
-                    && !history.contains(coord)
-            });
-
-        for coord in next_visits {
-            to_visit.push_back(
-                Visitation {
-                    pos: *coord,
-                    dist: visitation.dist + 1,
-                }
-            );
-        }
-    }
-
-    None
+    search::bfs(&vault_graph(vault), p1, |pos| *pos == p2).map(|found| found.cost)
 }
 
 fn key_to_idx(key: char) -> usize {
@@ -506,6 +439,7 @@ pub fn search_for_keys(vault: &Vault, starts: &Vec<Coord>) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_parse_input() {
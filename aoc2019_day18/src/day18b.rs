@@ -1,4 +1,4 @@
-pub mod day18_utils;
+use aoc2019_day18::day18_utils;
 
 use std::time::Instant;
 
@@ -0,0 +1,28 @@
+pub mod day18_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day18_utils::*;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = (Vault, Coord);
+
+    fn get_day(&self) -> u32 {
+        18
+    }
+
+    fn parse(&self, input: &str) -> (Vault, Coord) {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, (vault, pos): &(Vault, Coord)) -> Answer {
+        search_for_keys(vault, &vec![*pos]).into()
+    }
+
+    fn solve_b(&self, (vault, pos): &(Vault, Coord)) -> Answer {
+        let (vault, positions) = replace_vault_center(vault, *pos);
+        search_for_keys(&vault, &positions).into()
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use aoc2019_utils::*;
+
+use crate::day19_cpu::*;
+
+pub type Coord = point_2d::Point2d<i64>;
+
+/// A query answer along with how many times the drone program had to be run
+/// to get it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BeamQuery<T> {
+    pub answer: T,
+    pub num_runs: usize,
+}
+
+/// The tractor beam as seen through the drone program. Every probe is
+/// memoized, and rows are described by their first and last affected x.
+///
+/// This relies on the beam being a cone coming out of the origin: each row's
+/// affected points are contiguous, and both edges move right at a (roughly)
+/// constant rate as y grows. That lets a row's edges be guessed by scaling
+/// the edges of the nearest known row and then corrected with a few probes,
+/// rather than scanning the row.
+pub struct TractorBeam {
+    prog: Vec<i64>,
+    probes: HashMap<Coord, bool>,
+    rows: BTreeMap<i64, Option<(i64, i64)>>,
+    num_runs: usize,
+}
+
+impl TractorBeam {
+    // How far (beyond the expected error) to look for the beam when a row's
+    // guessed edges both miss it.
+    const MIN_SEARCH_WINDOW: i64 = 4;
+
+    pub fn new(prog: &[i64]) -> Self {
+        Self {
+            prog: prog.to_vec(),
+            probes: HashMap::new(),
+            rows: BTreeMap::new(),
+            num_runs: 0,
+        }
+    }
+
+    pub fn get_num_runs(&self) -> usize {
+        self.num_runs
+    }
+
+    pub fn is_affected(&mut self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 {
+            return false;
+        }
+
+        let coord = Coord { x, y };
+        if let Some(affected) = self.probes.get(&coord) {
+            return *affected;
+        }
+
+        let mut cpu = Cpu::new(&self.prog);
+        cpu.set_print_output(false);
+        cpu.add_input(x);
+        cpu.add_input(y);
+        cpu.exec_prog();
+        let affected = cpu.pop_output().expect("the drone program gave no output") != 0;
+
+        self.num_runs += 1;
+        self.probes.insert(coord, affected);
+        affected
+    }
+
+    // The known, non-empty row closest to y (row 0 is useless for scaling).
+    fn get_reference_row(&self, y: i64) -> Option<(i64, (i64, i64))> {
+        self.rows.iter()
+            .filter(|(row_y, edges)| **row_y > 0 && edges.is_some())
+            .map(|(row_y, edges)| (*row_y, edges.unwrap()))
+            .min_by_key(|(row_y, _)| (row_y - y).abs())
+    }
+
+    fn find_any_affected(&mut self, y: i64, guess: i64, window: i64) -> Option<i64> {
+        (0..=window)
+            .flat_map(|dist| vec![guess + dist, guess - dist])
+            .filter(|x| *x >= 0)
+            .find(|x| self.is_affected(*x, y))
+    }
+
+    /// The first and last affected x in row y, or `None` if the row misses
+    /// the beam entirely (which happens close to the origin).
+    pub fn get_row_edges(&mut self, y: i64) -> Option<(i64, i64)> {
+        if let Some(edges) = self.rows.get(&y) {
+            return *edges;
+        }
+
+        let (guess_start, guess_end, window) = match self.get_reference_row(y) {
+            None => (0, 0, std::cmp::max(4 * y, Self::MIN_SEARCH_WINDOW)),
+            Some((ref_y, (ref_start, ref_end))) => {
+                let guess_start = ref_start * y / ref_y;
+                let guess_end = ref_end * y / ref_y;
+                // scaling multiplies the rounding error in the reference edges
+                let error = y / ref_y + 1;
+                (guess_start, guess_end, guess_end - guess_start + error
+                    + Self::MIN_SEARCH_WINDOW)
+            },
+        };
+
+        let edges = self.find_any_affected(y, guess_start, window)
+            .or_else(|| self.find_any_affected(y, guess_end, window))
+            .map(|hit_x| {
+                let mut start = hit_x;
+                while self.is_affected(start - 1, y) {
+                    start -= 1;
+                }
+
+                let mut end = std::cmp::max(hit_x, guess_end);
+                if self.is_affected(end, y) {
+                    while self.is_affected(end + 1, y) {
+                        end += 1;
+                    }
+                } else {
+                    while !self.is_affected(end, y) {
+                        end -= 1;
+                    }
+                }
+
+                (start, end)
+            });
+
+        self.rows.insert(y, edges);
+        edges
+    }
+
+    fn query<T, F>(&mut self, f: F) -> BeamQuery<T>
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let start_runs = self.num_runs;
+        let answer = f(self);
+        BeamQuery { answer, num_runs: self.num_runs - start_runs }
+    }
+
+    /// How many points in the rectangle with top left corner (left, top) are
+    /// affected by the beam.
+    pub fn count_affected(&mut self, left: i64, top: i64, width: i64, height: i64)
+    -> BeamQuery<usize> {
+        self.query(|beam| {
+            let right = left + width - 1;
+            (top..(top + height))
+                .filter_map(|y| beam.get_row_edges(y))
+                .map(|(start, end)| {
+                    let start = std::cmp::max(start, left);
+                    let end = std::cmp::min(end, right);
+                    std::cmp::max(end - start + 1, 0) as usize
+                })
+                .sum()
+        })
+    }
+
+    // Whether a square of the given size fits with its bottom left corner at
+    // the start of row y. Returns the square's top left corner if so.
+    fn fit_square_at(&mut self, y: i64, size: i64) -> Option<Coord> {
+        let top = y - size + 1;
+        if top < 0 {
+            return None;
+        }
+        let (start, _) = self.get_row_edges(y)?;
+        let (_, top_end) = self.get_row_edges(top)?;
+        if top_end >= start + size - 1 {
+            Some(Coord { x: start, y: top })
+        } else {
+            None
+        }
+    }
+
+    /// The top left corner of the closest square of the given size that fits
+    /// entirely in the beam. The widening cone means a square that fits at
+    /// one row fits at every row below it, so this gallops down to a fitting
+    /// row and then binary searches back up.
+    pub fn find_square(&mut self, size: i64) -> BeamQuery<Option<Coord>> {
+        // how far to look past the binary search for rows where rounding made
+        // the fit test non-monotonic
+        const SLACK_ROWS: i64 = 8;
+        const MAX_ROW: i64 = 1 << 40;
+
+        self.query(|beam| {
+            let mut lo = size - 1;
+            let mut hi = std::cmp::max(lo, 1);
+            while beam.fit_square_at(hi, size).is_none() {
+                lo = hi;
+                hi *= 2;
+                if hi > MAX_ROW {
+                    return None;
+                }
+            }
+
+            while hi - lo > 1 {
+                let mid = lo + (hi - lo) / 2;
+                if beam.fit_square_at(mid, size).is_some() {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+
+            let first_y = ((hi - SLACK_ROWS)..hi)
+                .filter(|y| *y >= size - 1)
+                .find(|y| beam.fit_square_at(*y, size).is_some())
+                .unwrap_or(hi);
+            beam.fit_square_at(first_y, size)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A beam between the lines x = y / 2 and x = y. Each program reads x and
+    // y and prints 1 if 2x >= y and x <= y.
+    //
+    //   in x -> [100], in y -> [101]
+    //   [102] = 2 * [100]
+    //   [103] = [102] < [101]  (left of the beam)
+    //   [104] = [101] < [100]  (right of the beam)
+    //   [105] = [103] + [104]
+    //   [106] = [105] == 0
+    //   out [106]
+    const CONE_PROG: &str = "3,100,3,101,1002,100,2,102,7,102,101,103,7,101,100,104,\
+        1,103,104,105,1008,105,0,106,4,106,99";
+
+    fn is_in_cone(x: i64, y: i64) -> bool {
+        2 * x >= y && x <= y
+    }
+
+    #[test]
+    fn test_is_affected_memoized() {
+        let mut beam = TractorBeam::new(&parse_prog(CONE_PROG));
+        assert!(beam.is_affected(3, 5));
+        assert!(!beam.is_affected(2, 5));
+        assert!(!beam.is_affected(6, 5));
+        assert!(beam.is_affected(3, 5));
+        assert_eq!(beam.get_num_runs(), 3);
+    }
+
+    #[test]
+    fn test_get_row_edges() {
+        let mut beam = TractorBeam::new(&parse_prog(CONE_PROG));
+        assert_eq!(beam.get_row_edges(0), Some((0, 0)));
+        assert_eq!(beam.get_row_edges(10), Some((5, 10)));
+        assert_eq!(beam.get_row_edges(11), Some((6, 11)));
+        assert_eq!(beam.get_row_edges(1000), Some((500, 1000)));
+        assert_eq!(beam.get_row_edges(1001), Some((501, 1001)));
+    }
+
+    #[test]
+    fn test_count_affected() {
+        let mut beam = TractorBeam::new(&parse_prog(CONE_PROG));
+        let target = (0..50).flat_map(|y| (0..50).map(move |x| (x, y)))
+            .filter(|(x, y)| is_in_cone(*x, *y))
+            .count();
+        let result = beam.count_affected(0, 0, 50, 50);
+        assert_eq!(result.answer, target);
+        assert!(result.num_runs < 2500 / 4);
+
+        let result = beam.count_affected(0, 0, 50, 50);
+        assert_eq!(result, BeamQuery { answer: target, num_runs: 0 });
+
+        let target = (20..30).flat_map(|y| (10..15).map(move |x| (x, y)))
+            .filter(|(x, y)| is_in_cone(*x, *y))
+            .count();
+        assert_eq!(beam.count_affected(10, 20, 5, 10).answer, target);
+    }
+
+    #[test]
+    fn test_find_square() {
+        let mut beam = TractorBeam::new(&parse_prog(CONE_PROG));
+
+        // brute force over the same cone
+        let size = 10;
+        let fits = |x: i64, y: i64| {
+            is_in_cone(x, y + size - 1) && is_in_cone(x + size - 1, y)
+        };
+        let target = (0..100).flat_map(|y| (0..100).map(move |x| (x, y)))
+            .find(|(x, y)| fits(*x, *y))
+            .map(|(x, y)| Coord { x, y });
+
+        let result = beam.find_square(size);
+        assert_eq!(result.answer, target);
+        assert!(result.num_runs < 200);
+    }
+}
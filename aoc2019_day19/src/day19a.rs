@@ -1,30 +1,31 @@
-pub mod day19_cpu;
+use aoc2019_day19::day19_cpu;
+use aoc2019_day19::day19_utils;
 
 use day19_cpu::*;
+use day19_utils::*;
 
 fn main() {
     let input = aoc2019_utils::get_input("inputs/day19.txt");
     let prog = parse_prog(&input);
 
-    let mut num_affected_points = 0;
-
-    println!("the grid:");
     const GRID_WIDTH: i64 = 50;
     const GRID_HEIGHT: i64 = 50;
+
+    let mut beam = TractorBeam::new(&prog);
+    let result = beam.count_affected(0, 0, GRID_WIDTH, GRID_HEIGHT);
+
+    println!("the grid:");
     for y in 0..GRID_HEIGHT {
-        for x in 0..GRID_WIDTH {
-            let mut cpu = Cpu::new(&prog);
-            cpu.set_print_output(false);
-            cpu.add_input(x);
-            cpu.add_input(y);
-            cpu.exec_prog();
-            let is_affected = cpu.pop_output().unwrap() != 0;
-            let out_char = if is_affected { '#' } else { '.' };
-            num_affected_points += if is_affected { 1 } else { 0 };
-            print!("{}", out_char);
-        }
-        println!("");
+        let edges = beam.get_row_edges(y);
+        let row = (0..GRID_WIDTH)
+            .map(|x| match edges {
+                Some((start, end)) if x >= start && x <= end => '#',
+                _ => '.',
+            })
+            .collect::<String>();
+        println!("{}", row);
     }
 
-    println!("affected points: {}", num_affected_points);
+    println!("affected points: {}", result.answer);
+    println!("program runs: {} (of {} points)", result.num_runs, GRID_WIDTH * GRID_HEIGHT);
 }
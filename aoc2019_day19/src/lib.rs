@@ -0,0 +1,33 @@
+pub mod day19_cpu;
+pub mod day19_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day19_cpu::*;
+use day19_utils::*;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        19
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        let mut beam = TractorBeam::new(prog);
+        beam.count_affected(0, 0, 50, 50).answer.into()
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        let mut beam = TractorBeam::new(prog);
+        beam.find_square(100).answer
+            .map(|corner| corner.x * 10000 + corner.y)
+            .into()
+    }
+}
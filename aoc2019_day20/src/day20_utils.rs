@@ -1,10 +1,8 @@
-use std::collections::HashSet;
 use std::collections::HashMap;
-use std::collections::LinkedList;
 
 use aoc2019_utils::*;
 
-pub type Coord = point_2d::Point2d<u8>;
+pub type Coord = grid::GridCoord;
 
 const ZERO_COORD: Coord = Coord { x: 0, y: 0 };
 
@@ -22,7 +20,7 @@ pub enum Tile {
     Portal(PortalDesc),
 }
 
-pub type MazeTiles = Vec<Vec<Tile>>;
+pub type MazeTiles = grid::Grid<Tile>;
 
 pub struct Maze {
     tiles: MazeTiles,
@@ -47,7 +45,7 @@ fn parse_maze_marker(
 ) -> Option<(u8, u8, Coord, Coord, bool)> {
     let get_char = |x: usize, y: usize| { lines[y].as_bytes()[x] };
     let is_char = |c| { b'A' <= c && c <= b'Z' };
-    let to_coord = |x: usize, y: usize| Coord { x: x as u8, y: y as u8 };
+    let to_coord = |x: usize, y: usize| Coord { x: x as i32, y: y as i32 };
 
     if ((x > 0) && is_char(get_char(x - 1, y)))
         || ((y > 0) && is_char(get_char(x, y - 1)))
@@ -116,7 +114,7 @@ pub fn parse_input(input: &str) -> Maze {
     let width = lines[0].len();
 
     let mut maze = Maze {
-        tiles: vec![vec![Tile::None; height]; width],
+        tiles: MazeTiles::new(width, height, Tile::None),
         start: ZERO_COORD,
         end: ZERO_COORD,
     };
@@ -125,9 +123,10 @@ pub fn parse_input(input: &str) -> Maze {
 
     let mut handle_char_at = |x: usize, y: usize| {
         let c = lines[y].as_bytes()[x];
+        let coord = Coord { x: x as i32, y: y as i32 };
         match c {
-            b'#' => maze.tiles[x][y] = Tile::Wall,
-            b'.' => maze.tiles[x][y] = Tile::Space,
+            b'#' => maze.tiles[coord] = Tile::Wall,
+            b'.' => maze.tiles[coord] = Tile::Space,
             b'A'..=b'Z' => {
                 match parse_maze_marker(&lines, x, y, width, height) {
                     Some((c1, c2, send_coord, recv_coord, is_outer)) => {
@@ -138,10 +137,8 @@ pub fn parse_input(input: &str) -> Maze {
                         } else if let Some((other_send, other_recv, other_is_outer)) =
                             portals.remove(&(c1, c2))
                         {
-                            maze.tiles[send_coord.x as usize][send_coord.y as usize] =
-                                to_portal_tile(other_recv, is_outer);
-                            maze.tiles[other_send.x as usize][other_send.y as usize] =
-                                to_portal_tile(recv_coord, other_is_outer);
+                            maze.tiles[send_coord] = to_portal_tile(other_recv, is_outer);
+                            maze.tiles[other_send] = to_portal_tile(recv_coord, other_is_outer);
                         } else {
                             portals.insert(
                                 (c1, c2),
@@ -166,125 +163,41 @@ pub fn parse_input(input: &str) -> Maze {
     maze
 }
 
-fn get_reachable_coords(pos: Coord, width: usize, height: usize) -> Vec<Coord> {
-    let mut coords = Vec::with_capacity(4);
-    if pos.y > 0 {
-        coords.push(Coord { x: pos.x, y: pos.y - 1 });
-    }
-    if pos.y < height as u8 - 1 {
-        coords.push(Coord { x: pos.x, y: pos.y + 1 });
-    }
-    if pos.x > 0 {
-        coords.push(Coord { x: pos.x - 1, y: pos.y });
-    }
-    if pos.x < width as u8 - 1 {
-        coords.push(Coord { x: pos.x + 1, y: pos.y });
-    }
-
-    coords
-}
-
-pub fn traverse_maze(maze: &Maze) -> Option<u32> {
-
-    struct SearchState {
-        loc: Coord,
-        num_steps: u32,
-    }
-
-    let width = maze.tiles.len();
-    let height = maze.tiles[0].len();
-
-    let mut history = HashSet::new();
-    let mut to_visit = LinkedList::new();
+/// Stepping onto a portal moves straight to its destination.
+impl search::Graph for Maze {
+    type Node = Coord;
 
-    to_visit.push_back(
-        SearchState {
-            loc: maze.start,
-            num_steps: 0,
-        }
-    );
-
-    loop {
-        if to_visit.is_empty() {
-            return None;
-        }
-
-        let cur_state = to_visit.pop_front().unwrap();
-        if cur_state.loc == maze.end {
-            return Some(cur_state.num_steps);
-        }
-
-        history.insert(cur_state.loc.clone());
-
-        let next_coords = get_reachable_coords(cur_state.loc, width, height);
-        to_visit.extend(next_coords.into_iter()
+    fn edges(&self, loc: &Coord) -> Vec<(Coord, u32)> {
+        self.tiles.neighbours4(*loc)
             .filter_map(|c| {
-                match maze.tiles[c.x as usize][c.y as usize] {
-                    Tile::Space => Some(c),
-                    Tile::Portal(desc) => Some(desc.dest),
+                match self.tiles[c] {
+                    Tile::Space => Some((c, 1)),
+                    Tile::Portal(desc) => Some((desc.dest, 1)),
                     _ => None,
                 }
             })
-            .filter(|c| !history.contains(&c))
-            .map(|c| SearchState {
-                loc: c,
-                num_steps: cur_state.num_steps + 1,
-            })
-        );
+            .collect()
     }
 }
 
-pub fn traverse_recursive_maze(maze: &Maze) -> Option<u32> {
-
-    #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
-    struct SearchVisit {
-        loc: Coord,
-        depth: u16,
-    }
-
-    struct SearchState {
-        loc: Coord,
-        depth: u16,
-        num_steps: u32,
-    }
-
-    let width = maze.tiles.len();
-    let height = maze.tiles[0].len();
-
-    let mut history = HashSet::new();
-    let mut to_visit = LinkedList::new();
-
-    to_visit.push_back(
-        SearchState {
-            loc: maze.start,
-            depth: 0,
-            num_steps: 0,
-        }
-    );
-
-    loop {
-        if to_visit.is_empty() {
-            return None;
-        }
+/// The maze where inner portals lead a level down and outer portals a level
+/// up. Outer portals are closed on the top level and the start and end are
+/// walls on every other level.
+pub struct RecursiveMaze<'a>(pub &'a Maze);
 
-        let cur_state = to_visit.pop_front().unwrap();
-        if (cur_state.loc == maze.end) && (cur_state.depth == 0) {
-            return Some(cur_state.num_steps);
-        }
+impl<'a> search::Graph for RecursiveMaze<'a> {
+    /// A location and the depth it's at.
+    type Node = (Coord, u16);
 
-        history.insert(
-            SearchVisit {
-                loc: cur_state.loc,
-                depth: cur_state.depth,
-            }
-        );
+    fn edges(&self, node: &(Coord, u16)) -> Vec<((Coord, u16), u32)> {
+        let maze = self.0;
+        let (loc, depth) = *node;
 
-        let next_coords = get_reachable_coords(cur_state.loc, width, height);
-        to_visit.extend(next_coords.into_iter()
+        maze.tiles.neighbours4(loc)
             .filter_map(|c| {
-                let tile = maze.tiles[c.x as usize][c.y as usize];
+                let tile = maze.tiles[c];
 
-                if cur_state.depth == 0 {
+                if depth == 0 {
                     if let Tile::Portal(desc) = tile {
                         if desc.is_outer {
                             return None;
@@ -295,38 +208,31 @@ pub fn traverse_recursive_maze(maze: &Maze) -> Option<u32> {
                 }
 
                 match tile {
-                    Tile::Space => Some(
-                        SearchVisit {
-                            loc: c,
-                            depth: cur_state.depth,
-                        }
-                    ),
+                    Tile::Space => Some(((c, depth), 1)),
                     Tile::Portal(desc) => {
-                        let new_depth = if desc.is_outer {
-                            cur_state.depth - 1
-                        } else {
-                            cur_state.depth + 1
-                        };
-                        Some(
-                            SearchVisit {
-                                loc: desc.dest,
-                                depth: new_depth,
-                            }
-                        )
+                        let new_depth = if desc.is_outer { depth - 1 } else { depth + 1 };
+                        Some(((desc.dest, new_depth), 1))
                     },
                     _ => None,
                 }
             })
-            .filter(|search_visit| !history.contains(&search_visit))
-            .map(|search_visit| SearchState {
-                loc: search_visit.loc,
-                depth: search_visit.depth,
-                num_steps: cur_state.num_steps + 1,
-            })
-        );
+            .collect()
     }
 }
 
+pub fn find_maze_path(maze: &Maze) -> Option<Vec<Coord>> {
+    search::bfs(maze, maze.start, |loc| *loc == maze.end).map(|found| found.path)
+}
+
+pub fn traverse_maze(maze: &Maze) -> Option<u32> {
+    search::bfs(maze, maze.start, |loc| *loc == maze.end).map(|found| found.cost)
+}
+
+pub fn traverse_recursive_maze(maze: &Maze) -> Option<u32> {
+    search::bfs(&RecursiveMaze(maze), (maze.start, 0), |node| *node == (maze.end, 0))
+        .map(|found| found.cost)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,14 +349,14 @@ mod tests {
             to_portal_tile(Coord { x: x, y: y }, is_outer)
         };
 
-        assert_eq!(maze.tiles[9][7], to_portal_tile_xy(2, 8, false));
-        assert_eq!(maze.tiles[1][8], to_portal_tile_xy(9, 6, true));
+        assert_eq!(maze.tiles[Coord { x: 9, y: 7 }], to_portal_tile_xy(2, 8, false));
+        assert_eq!(maze.tiles[Coord { x: 1, y: 8 }], to_portal_tile_xy(9, 6, true));
 
-        assert_eq!(maze.tiles[7][10], to_portal_tile_xy(2, 13, false));
-        assert_eq!(maze.tiles[1][13], to_portal_tile_xy(6, 10, true));
+        assert_eq!(maze.tiles[Coord { x: 7, y: 10 }], to_portal_tile_xy(2, 13, false));
+        assert_eq!(maze.tiles[Coord { x: 1, y: 13 }], to_portal_tile_xy(6, 10, true));
 
-        assert_eq!(maze.tiles[11][11], to_portal_tile_xy(2, 15, false));
-        assert_eq!(maze.tiles[1][15], to_portal_tile_xy(11, 12, true));
+        assert_eq!(maze.tiles[Coord { x: 11, y: 11 }], to_portal_tile_xy(2, 15, false));
+        assert_eq!(maze.tiles[Coord { x: 1, y: 15 }], to_portal_tile_xy(11, 12, true));
 
 
         let maze = parse_input(SAMPLE_MAZE_2);
@@ -467,6 +373,11 @@ mod tests {
         let maze = parse_input(SAMPLE_MAZE_2);
         let result = traverse_maze(&maze);
         assert_eq!(result, Some(58));
+
+        let path = find_maze_path(&maze).unwrap();
+        assert_eq!(path.len(), 59);
+        assert_eq!(path[0], maze.start);
+        assert_eq!(path[58], maze.end);
     }
 
     #[test]
@@ -1,4 +1,4 @@
-pub mod day20_utils;
+use aoc2019_day20::day20_utils;
 
 use day20_utils::*;
 
@@ -0,0 +1,27 @@
+pub mod day20_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day20_utils::*;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Maze;
+
+    fn get_day(&self) -> u32 {
+        20
+    }
+
+    fn parse(&self, input: &str) -> Maze {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, maze: &Maze) -> Answer {
+        traverse_maze(maze).into()
+    }
+
+    fn solve_b(&self, maze: &Maze) -> Answer {
+        traverse_recursive_maze(maze).into()
+    }
+}
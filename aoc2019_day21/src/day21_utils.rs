@@ -0,0 +1,61 @@
+use crate::day21_cpu::*;
+
+// !(A && B && C) && D
+pub const WALK_SCRIPT: &str = concat!(
+    // J = !(A && B && C)
+    "NOT T T\n",
+    "AND A T\n",
+    "AND B T\n",
+    "AND C T\n",
+    "NOT T J\n",
+    // J &= D
+    "NOT D T\n",
+    "NOT T T\n",
+    "AND T J\n",
+    "WALK\n",
+);
+
+// death = !D || (!E && !H) = !(D && !(!E && !H))
+// hole = !(A && B && C)
+// jump = hole && !death = !(A && B && C) && D && !(!E && !H)
+pub const RUN_SCRIPT: &str = concat!(
+    // J = !(!E && !H)
+    "NOT E T\n",
+    "NOT H J\n",
+    "AND T J\n",
+    "NOT J J\n",
+    // J &= D
+    "AND D J\n",
+    // T = !(A && B && C)
+    "NOT A T\n",
+    "NOT T T\n",
+    "AND B T\n",
+    "AND C T\n",
+    "NOT T T\n",
+    // J &= T
+    "AND T J\n",
+    "RUN\n",
+);
+
+/// Runs a springscript and returns the hull damage, or, if the droid didn't
+/// make it, what the droid printed instead.
+pub fn run_springscript(prog: &[i64], script: &str) -> Result<i64, String> {
+    let script_input = script.bytes()
+        .map(|c| c as i64)
+        .collect::<Vec<i64>>();
+
+    let mut cpu = Cpu::new(prog);
+    cpu.set_print_output(false);
+    cpu.add_input_from_slice(&script_input);
+    cpu.exec_prog();
+
+    let output = cpu.get_output();
+    match output.last() {
+        None => Err("NO OUTPUT!".to_string()),
+        Some(last_val) if *last_val > 127 => Ok(*last_val),
+        Some(_) => {
+            let out_bytes = output.iter().map(|o| *o as u8).collect::<Vec<u8>>();
+            Err(String::from_utf8_lossy(&out_bytes).into_owned())
+        },
+    }
+}
@@ -0,0 +1,37 @@
+pub mod day21_cpu;
+pub mod day21_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day21_cpu::*;
+use day21_utils::*;
+
+// If the droid falls, what it printed is the closest thing to an answer.
+fn get_damage(prog: &[i64], script: &str) -> Answer {
+    match run_springscript(prog, script) {
+        Ok(damage) => damage.into(),
+        Err(output) => output.into(),
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed = Vec<i64>;
+
+    fn get_day(&self) -> u32 {
+        21
+    }
+
+    fn parse(&self, input: &str) -> Vec<i64> {
+        parse_prog(input)
+    }
+
+    fn solve_a(&self, prog: &Vec<i64>) -> Answer {
+        get_damage(prog, WALK_SCRIPT)
+    }
+
+    fn solve_b(&self, prog: &Vec<i64>) -> Answer {
+        get_damage(prog, RUN_SCRIPT)
+    }
+}
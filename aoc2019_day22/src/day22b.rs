@@ -1,4 +1,4 @@
-pub mod day22_utils;
+use aoc2019_day22::day22_utils;
 
 // use aoc2019_utils::*;
 use day22_utils::*;
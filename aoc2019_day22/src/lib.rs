@@ -0,0 +1,40 @@
+pub mod day22_utils;
+
+use aoc2019_utils::solution::{Answer, Solution};
+
+use day22_utils::*;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Vec<ShuffleType>;
+
+    fn get_day(&self) -> u32 {
+        22
+    }
+
+    fn parse(&self, input: &str) -> Vec<ShuffleType> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, shuffles: &Vec<ShuffleType>) -> Answer {
+        let mut deck = Deck::new(10007);
+        deck.shuffle_multi(shuffles);
+        deck.find_card_position(2019).into()
+    }
+
+    fn solve_b(&self, shuffles: &Vec<ShuffleType>) -> Answer {
+        const DECK_SIZE: u64 = 119315717514047;
+        const NUM_SHUFFLES: u64 = 101741582076661;
+        const SHUFFLE_STACK_CHUNK_SIZE: u64 = 10000000;
+
+        let deck = BigDeck2::new(DECK_SIZE);
+        let shuffle = deck.compile(shuffles);
+        let shuffle_stacked = deck.stack_shuffle(
+            &shuffle,
+            NUM_SHUFFLES,
+            SHUFFLE_STACK_CHUNK_SIZE,
+        );
+        deck.reverse_shuffle(&shuffle_stacked, 2020).into()
+    }
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use crate::point_2d::Point2d;
+
+pub type GridCoord = Point2d<i32>;
+
+/// A fixed size, dense grid stored row by row. (0, 0) is the top left
+/// corner and y grows downward, the way the puzzles print their maps.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Wraps cells that are already in row-major order.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Result<Self, String> {
+        if width == 0 {
+            return if cells.is_empty() {
+                Ok(Self { cells, width: 0, height: 0 })
+            } else {
+                Err("a grid with cells can't have a width of 0".to_string())
+            };
+        }
+        let height = cells.len() / width;
+        if height * width != cells.len() {
+            return Err(format!("{} cells don't make whole rows of {}", cells.len(), width));
+        }
+        Ok(Self { cells, width, height })
+    }
+
+    /// Parses one row per line. `mapper` turns each char into a cell, and
+    /// returning `None` rejects the char. Every line has to be the same
+    /// length.
+    pub fn parse<F>(text: &str, mut mapper: F) -> Result<Self, String>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+
+        for (y, line) in text.lines().enumerate() {
+            let mut line_len = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = mapper(c)
+                    .ok_or_else(|| format!("bad grid char '{}' at {}, {}", c, x, y))?;
+                cells.push(cell);
+                line_len += 1;
+            }
+
+            match width {
+                None => width = Some(line_len),
+                Some(width) if width != line_len => {
+                    return Err(format!(
+                        "line {} is {} chars long but the grid is {} wide", y, line_len, width));
+                },
+                Some(_) => {},
+            }
+        }
+
+        Self::from_cells(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn in_bounds(&self, coord: GridCoord) -> bool {
+        coord.x >= 0 && coord.y >= 0
+            && (coord.x as usize) < self.width && (coord.y as usize) < self.height
+    }
+
+    fn get_idx(&self, coord: GridCoord) -> Option<usize> {
+        if self.in_bounds(coord) {
+            Some(coord.y as usize * self.width + coord.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn get_coord(&self, idx: usize) -> GridCoord {
+        GridCoord { x: (idx % self.width) as i32, y: (idx / self.width) as i32 }
+    }
+
+    pub fn get(&self, coord: GridCoord) -> Option<&T> {
+        self.get_idx(coord).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, coord: GridCoord) -> Option<&mut T> {
+        match self.get_idx(coord) {
+            Some(idx) => Some(&mut self.cells[idx]),
+            None => None,
+        }
+    }
+
+    /// Replaces the cell at `coord`, returning the old value. Returns `None`
+    /// (and changes nothing) if `coord` is off the grid.
+    pub fn set(&mut self, coord: GridCoord, val: T) -> Option<T> {
+        self.get_mut(coord).map(|cell| std::mem::replace(cell, val))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Every coordinate, in reading order.
+    pub fn coords(&self) -> impl Iterator<Item = GridCoord> {
+        let width = self.width as i32;
+        let height = self.height as i32;
+        (0..height).flat_map(move |y| (0..width).map(move |x| GridCoord { x, y }))
+    }
+
+    /// Every cell along with its coordinate, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridCoord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// The coordinate of the first cell (in reading order) matching `pred`.
+    pub fn find<P>(&self, pred: P) -> Option<GridCoord>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.iter().position(pred).map(|idx| self.get_coord(idx))
+    }
+
+    /// The up to 4 orthogonal neighbours of `coord` that are on the grid.
+    pub fn neighbours4(&self, coord: GridCoord) -> impl Iterator<Item = GridCoord> + '_ {
+        coord.neighbours4().filter(move |next| self.in_bounds(*next))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours of `coord` that are on
+    /// the grid.
+    pub fn neighbours8(&self, coord: GridCoord) -> impl Iterator<Item = GridCoord> + '_ {
+        coord.neighbours8().filter(move |next| self.in_bounds(*next))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(std::cmp::max(self.width, 1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is off a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    /// Draws the grid one row per line, using `to_char` for each cell.
+    pub fn render<F>(&self, mut to_char: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut txt = String::with_capacity((self.width + 1) * self.height);
+        self.rows().for_each(|row| {
+            row.iter().for_each(|cell| txt.push(to_char(cell)));
+            txt.push('\n');
+        });
+        txt
+    }
+}
+
+impl<T> Index<GridCoord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: GridCoord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is off a {}x{} grid", coord, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<GridCoord> for Grid<T> {
+    fn index_mut(&mut self, coord: GridCoord) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is off a {}x{} grid", coord, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An unbounded grid that only stores the cells that have been set. Every
+/// other cell reads as the default value. The bounds grow to cover every
+/// cell that's been set.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<GridCoord, T>,
+    default: T,
+    bounds: Option<(GridCoord, GridCoord)>,
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self { cells: HashMap::new(), default, bounds: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: GridCoord) -> bool {
+        self.cells.contains_key(&coord)
+    }
+
+    pub fn get(&self, coord: GridCoord) -> &T {
+        self.cells.get(&coord).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, coord: GridCoord, val: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (coord, coord),
+            Some((min, max)) => (
+                GridCoord { x: std::cmp::min(min.x, coord.x), y: std::cmp::min(min.y, coord.y) },
+                GridCoord { x: std::cmp::max(max.x, coord.x), y: std::cmp::max(max.y, coord.y) },
+            ),
+        });
+        self.cells.insert(coord, val)
+    }
+
+    /// The top left and bottom right corners of every cell that's been set,
+    /// both included.
+    pub fn bounds(&self) -> Option<(GridCoord, GridCoord)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.map(|(min, max)| (max.x - min.x + 1) as usize).unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.map(|(min, max)| (max.y - min.y + 1) as usize).unwrap_or(0)
+    }
+
+    /// The cells that have been set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridCoord, &T)> {
+        self.cells.iter().map(|(coord, cell)| (*coord, cell))
+    }
+
+    /// Copies the bounded area into a dense grid. The dense grid's (0, 0) is
+    /// the top left corner of the bounds, which is returned along with it.
+    pub fn to_grid(&self) -> (Grid<T>, GridCoord)
+    where
+        T: Clone,
+    {
+        let origin = self.bounds.map(|(min, _)| min).unwrap_or(GridCoord { x: 0, y: 0 });
+        let mut grid = Grid::new(self.width(), self.height(), self.default.clone());
+        self.iter().for_each(|(coord, cell)| {
+            grid[coord - origin] = cell.clone();
+        });
+        (grid, origin)
+    }
+}
+
+impl<T: fmt::Display + Clone> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_grid().0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_grid() -> Grid<char> {
+        Grid::parse("#..\n.#.\n..#\n#..\n", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[GridCoord { x: 1, y: 1 }], '#');
+        assert_eq!(grid[GridCoord { x: 0, y: 3 }], '#');
+        assert_eq!(grid.get(GridCoord { x: 3, y: 0 }), None);
+        assert_eq!(grid.get(GridCoord { x: 0, y: -1 }), None);
+        assert_eq!(grid.to_string(), "#..\n.#.\n..#\n#..\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let to_bool = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(Grid::parse("#.\n.#\n", to_bool).unwrap().cells(), &[true, false, false, true]);
+        assert!(Grid::parse("#.\n.x\n", to_bool).unwrap_err().contains("'x' at 1, 1"));
+        assert!(Grid::parse("#.\n.\n", to_bool).is_err());
+        assert_eq!(Grid::parse("", to_bool).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_from_cells() {
+        assert_eq!(Grid::from_cells(2, vec![1, 2, 3, 4]).unwrap().height(), 2);
+        assert!(Grid::from_cells(2, vec![1, 2, 3]).is_err());
+        assert!(Grid::from_cells(0, vec![1]).is_err());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = example_grid();
+        assert_eq!(grid.set(GridCoord { x: 2, y: 0 }, '@'), Some('.'));
+        assert_eq!(grid.set(GridCoord { x: 5, y: 0 }, '@'), None);
+        grid[GridCoord { x: 0, y: 0 }] = '.';
+        assert_eq!(grid.row(0), &['.', '.', '@']);
+        assert_eq!(grid.find(|c| *c == '#'), Some(GridCoord { x: 1, y: 1 }));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid = example_grid();
+        let _ = grid[GridCoord { x: 3, y: 3 }];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example_grid();
+        let corner = GridCoord { x: 0, y: 0 };
+        assert_eq!(grid.neighbours4(corner).collect::<Vec<GridCoord>>(), vec![
+            GridCoord { x: 1, y: 0 },
+            GridCoord { x: 0, y: 1 },
+        ]);
+        assert_eq!(grid.neighbours8(corner).count(), 3);
+        assert_eq!(grid.neighbours4(GridCoord { x: 1, y: 1 }).count(), 4);
+        assert_eq!(grid.neighbours8(GridCoord { x: 1, y: 1 }).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example_grid();
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(grid.row(2), &['.', '.', '#']);
+        assert_eq!(grid.column(0).collect::<String>(), "#..#");
+        assert_eq!(grid.column(2).collect::<String>(), "..#.");
+        assert_eq!(
+            grid.iter().filter(|(_, c)| **c == '#').map(|(coord, _)| coord).collect::<Vec<_>>(),
+            vec![
+                GridCoord { x: 0, y: 0 },
+                GridCoord { x: 1, y: 1 },
+                GridCoord { x: 2, y: 2 },
+                GridCoord { x: 0, y: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_map_and_render() {
+        let grid = example_grid().map(|c| *c == '#');
+        assert_eq!(grid.render(|wall| if *wall { 'X' } else { ' ' }), "X  \n X \n  X\nX  \n");
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.set(GridCoord { x: -2, y: 1 }, '#');
+        grid.set(GridCoord { x: 1, y: -1 }, '#');
+        assert_eq!(grid.set(GridCoord { x: 1, y: -1 }, '@'), Some('#'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(*grid.get(GridCoord { x: 100, y: 100 }), '.');
+        assert_eq!(grid.bounds(), Some((GridCoord { x: -2, y: -1 }, GridCoord { x: 1, y: 1 })));
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+
+        let (dense, origin) = grid.to_grid();
+        assert_eq!(origin, GridCoord { x: -2, y: -1 });
+        assert_eq!(dense[GridCoord { x: 3, y: 0 }], '@');
+        assert_eq!(grid.to_string(), "...@\n....\n#...\n");
+    }
+}
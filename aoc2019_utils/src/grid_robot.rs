@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::grid::SparseGrid;
+use crate::point_2d::Point2d;
+
+pub type RobotCoord = Point2d<i32>;
+
+/// The parts of an Intcode CPU a robot needs. Each day keeps its own copy of
+/// the CPU, so each day implements this for its `Cpu`.
+pub trait IntcodeMachine {
+    fn add_input(&mut self, input: i64);
+    /// Runs until the program needs more input or halts.
+    fn run(&mut self);
+    fn pop_output(&mut self) -> Option<i64>;
+    fn is_done(&self) -> bool;
+}
+
+pub use crate::point_2d::Direction as Dir;
+pub use crate::point_2d::Turn;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Pose {
+    pub pos: RobotCoord,
+    pub dir: Dir,
+}
+
+/// What the robot knows about (and does to) the grid it moves over.
+pub trait WorldModel {
+    type Cell: Copy;
+    fn get_cell(&self, pos: RobotCoord) -> Self::Cell;
+    fn set_cell(&mut self, pos: RobotCoord, cell: Self::Cell);
+}
+
+impl<C: Copy + Default> WorldModel for HashMap<RobotCoord, C> {
+    type Cell = C;
+
+    fn get_cell(&self, pos: RobotCoord) -> C {
+        self.get(&pos).copied().unwrap_or_default()
+    }
+
+    fn set_cell(&mut self, pos: RobotCoord, cell: C) {
+        self.insert(pos, cell);
+    }
+}
+
+impl<C: Copy> WorldModel for SparseGrid<C> {
+    type Cell = C;
+
+    fn get_cell(&self, pos: RobotCoord) -> C {
+        *self.get(pos)
+    }
+
+    fn set_cell(&mut self, pos: RobotCoord, cell: C) {
+        self.set(pos, cell);
+    }
+}
+
+/// Translates between the robot and its program: what to feed the CPU for a
+/// command, and what the CPU's answer does to the robot and the world.
+pub trait RobotProtocol {
+    type World: WorldModel;
+    type Command: Copy;
+    type Outcome;
+
+    /// How many outputs the program produces in answer to one input.
+    const NUM_OUTPUTS: usize;
+
+    fn encode(&self, pose: Pose, world: &Self::World, cmd: Self::Command) -> Vec<i64>;
+
+    fn apply(
+        &self,
+        pose: &mut Pose,
+        world: &mut Self::World,
+        cmd: Self::Command,
+        outputs: &[i64],
+    ) -> Self::Outcome;
+}
+
+pub trait FromIntcode {
+    fn from_intcode(num: i64) -> Self;
+}
+
+pub trait ToIntcode {
+    fn to_intcode(self) -> i64;
+}
+
+pub trait Obstacle {
+    fn is_obstacle(&self) -> bool;
+}
+
+impl FromIntcode for Turn {
+    fn from_intcode(num: i64) -> Self {
+        match num {
+            0 => Turn::Left,
+            1 => Turn::Right,
+            _ => panic!("invalid turn num: {}", num),
+        }
+    }
+}
+
+/// Color in, paint-and-turn out: the robot reports the color under it, then
+/// paints that cell, turns and moves forward one step.
+pub struct PaintProtocol<W> {
+    world: PhantomData<W>,
+}
+
+impl<W> Default for PaintProtocol<W> {
+    fn default() -> Self {
+        Self { world: PhantomData }
+    }
+}
+
+impl<W> Clone for PaintProtocol<W> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Painted<C> {
+    pub pos: RobotCoord,
+    pub cell: C,
+}
+
+impl<W> RobotProtocol for PaintProtocol<W>
+where
+    W: WorldModel,
+    W::Cell: FromIntcode + ToIntcode,
+{
+    type World = W;
+    type Command = ();
+    type Outcome = Painted<W::Cell>;
+
+    const NUM_OUTPUTS: usize = 2;
+
+    fn encode(&self, pose: Pose, world: &W, _cmd: ()) -> Vec<i64> {
+        vec![world.get_cell(pose.pos).to_intcode()]
+    }
+
+    fn apply(&self, pose: &mut Pose, world: &mut W, _cmd: (), outputs: &[i64])
+    -> Self::Outcome {
+        let painted = Painted {
+            pos: pose.pos,
+            cell: W::Cell::from_intcode(outputs[0]),
+        };
+        world.set_cell(painted.pos, painted.cell);
+        pose.dir = pose.dir.turn(Turn::from_intcode(outputs[1]));
+        pose.pos = pose.dir.move_coord(pose.pos);
+        painted
+    }
+}
+
+/// Move in, status out: the robot is told which way to go, and the program
+/// reports what it found there. The robot only moves if that wasn't an
+/// obstacle.
+pub struct MoveProtocol<W> {
+    world: PhantomData<W>,
+}
+
+impl<W> Default for MoveProtocol<W> {
+    fn default() -> Self {
+        Self { world: PhantomData }
+    }
+}
+
+impl<W> Clone for MoveProtocol<W> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Moved<C> {
+    pub pos: RobotCoord,
+    pub cell: C,
+    pub moved: bool,
+}
+
+pub fn get_move_command_num(dir: Dir) -> i64 {
+    match dir {
+        Dir::Up => 1,
+        Dir::Down => 2,
+        Dir::Left => 3,
+        Dir::Right => 4,
+    }
+}
+
+impl<W> RobotProtocol for MoveProtocol<W>
+where
+    W: WorldModel,
+    W::Cell: FromIntcode + Obstacle,
+{
+    type World = W;
+    type Command = Dir;
+    type Outcome = Moved<W::Cell>;
+
+    const NUM_OUTPUTS: usize = 1;
+
+    fn encode(&self, _pose: Pose, _world: &W, dir: Dir) -> Vec<i64> {
+        vec![get_move_command_num(dir)]
+    }
+
+    fn apply(&self, pose: &mut Pose, world: &mut W, dir: Dir, outputs: &[i64])
+    -> Self::Outcome {
+        let target = dir.move_coord(pose.pos);
+        let cell = W::Cell::from_intcode(outputs[0]);
+        world.set_cell(target, cell);
+
+        pose.dir = dir;
+        let moved = !cell.is_obstacle();
+        if moved {
+            pose.pos = target;
+        }
+
+        Moved { pos: target, cell, moved }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StepError {
+    /// The program halted without answering.
+    Halted,
+    /// The program halted partway through its answer.
+    PartialAnswer { outputs: Vec<i64>, expected: usize },
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Halted => write!(f, "the program halted"),
+            Self::PartialAnswer { outputs, expected } => {
+                write!(f, "the program halted after {} of {} outputs: {:?}",
+                    outputs.len(), expected, outputs)
+            },
+        }
+    }
+}
+
+/// A robot driven by an Intcode program. The protocol does all the
+/// puzzle-specific work; this just shuttles values between it and the CPU and
+/// keeps track of where the robot has been.
+#[derive(Clone)]
+pub struct GridRobot<M, P> {
+    cpu: M,
+    protocol: P,
+    pose: Pose,
+    history: Vec<Pose>,
+}
+
+impl<M, P> GridRobot<M, P>
+where
+    M: IntcodeMachine,
+    P: RobotProtocol,
+{
+    pub fn new(cpu: M, protocol: P, pose: Pose) -> Self {
+        Self {
+            cpu,
+            protocol,
+            pose,
+            history: vec![pose],
+        }
+    }
+
+    pub fn get_pose(&self) -> Pose {
+        self.pose
+    }
+
+    pub fn get_pos(&self) -> RobotCoord {
+        self.pose.pos
+    }
+
+    pub fn get_dir(&self) -> Dir {
+        self.pose.dir
+    }
+
+    /// Every pose the robot has been in, starting with the one it was
+    /// created with.
+    pub fn get_history(&self) -> &[Pose] {
+        &self.history
+    }
+
+    pub fn get_cpu(&self) -> &M {
+        &self.cpu
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cpu.is_done()
+    }
+
+    /// A copy of the robot, CPU state and all, whose history starts afresh
+    /// from where this one is. Cheaper than `clone` for a robot that has been
+    /// running a while.
+    pub fn fork(&self) -> Self
+    where
+        M: Clone,
+        P: Clone,
+    {
+        Self {
+            cpu: self.cpu.clone(),
+            protocol: self.protocol.clone(),
+            pose: self.pose,
+            history: vec![self.pose],
+        }
+    }
+
+    /// Sends one command to the program and applies its answer. Nothing
+    /// changes if the program halts before giving a full answer.
+    pub fn step(&mut self, world: &mut P::World, cmd: P::Command)
+    -> Result<P::Outcome, StepError> {
+        self.protocol.encode(self.pose, world, cmd).into_iter()
+            .for_each(|input| self.cpu.add_input(input));
+        self.cpu.run();
+
+        let mut outputs = Vec::with_capacity(P::NUM_OUTPUTS);
+        while outputs.len() < P::NUM_OUTPUTS {
+            match self.cpu.pop_output() {
+                Some(output) => outputs.push(output),
+                None if outputs.is_empty() => return Err(StepError::Halted),
+                None => {
+                    return Err(StepError::PartialAnswer { outputs, expected: P::NUM_OUTPUTS });
+                },
+            }
+        }
+
+        let outcome = self.protocol.apply(&mut self.pose, world, cmd, &outputs);
+        self.history.push(self.pose);
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stands in for a CPU: answers each input with a canned list of outputs
+    /// and halts once they run out.
+    #[derive(Clone)]
+    struct ScriptedMachine {
+        inputs: Vec<i64>,
+        replies: Vec<Vec<i64>>,
+        output: Vec<i64>,
+    }
+
+    impl ScriptedMachine {
+        fn new(replies: Vec<Vec<i64>>) -> Self {
+            Self { inputs: vec![], replies, output: vec![] }
+        }
+    }
+
+    impl IntcodeMachine for ScriptedMachine {
+        fn add_input(&mut self, input: i64) {
+            self.inputs.push(input);
+        }
+
+        fn run(&mut self) {
+            if !self.replies.is_empty() {
+                self.output.extend(self.replies.remove(0));
+            }
+        }
+
+        fn pop_output(&mut self) -> Option<i64> {
+            if self.output.is_empty() { None } else { Some(self.output.remove(0)) }
+        }
+
+        fn is_done(&self) -> bool {
+            self.replies.is_empty()
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+    enum Color { #[default] Black, White }
+
+    impl FromIntcode for Color {
+        fn from_intcode(num: i64) -> Self {
+            if num == 0 { Color::Black } else { Color::White }
+        }
+    }
+
+    impl ToIntcode for Color {
+        fn to_intcode(self) -> i64 {
+            if self == Color::Black { 0 } else { 1 }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+    enum Status { Wall, #[default] Open }
+
+    impl FromIntcode for Status {
+        fn from_intcode(num: i64) -> Self {
+            if num == 0 { Status::Wall } else { Status::Open }
+        }
+    }
+
+    impl Obstacle for Status {
+        fn is_obstacle(&self) -> bool {
+            *self == Status::Wall
+        }
+    }
+
+    const START: Pose = Pose { pos: RobotCoord { x: 0, y: 0 }, dir: Dir::Up };
+
+    #[test]
+    fn test_paint_protocol() {
+        let cpu = ScriptedMachine::new(vec![vec![1, 0], vec![0, 1]]);
+        let mut robot = GridRobot::new(cpu, PaintProtocol::default(), START);
+        let mut world = HashMap::new();
+
+        let outcome = robot.step(&mut world, ()).unwrap();
+        assert_eq!(outcome, Painted { pos: START.pos, cell: Color::White });
+        assert_eq!(robot.get_pose(), Pose { pos: RobotCoord { x: -1, y: 0 }, dir: Dir::Left });
+
+        robot.step(&mut world, ()).unwrap();
+        assert_eq!(robot.get_pose(), Pose { pos: RobotCoord { x: -1, y: -1 }, dir: Dir::Up });
+        assert_eq!(robot.cpu.inputs, vec![0, 0]);
+        assert_eq!(world.get_cell(START.pos), Color::White);
+        assert_eq!(world.get_cell(RobotCoord { x: -1, y: 0 }), Color::Black);
+        assert_eq!(robot.get_history().len(), 3);
+
+        assert!(robot.is_done());
+        assert_eq!(robot.step(&mut world, ()), Err(StepError::Halted));
+    }
+
+    #[test]
+    fn test_partial_answer() {
+        let cpu = ScriptedMachine::new(vec![vec![1]]);
+        let mut robot = GridRobot::new(cpu, PaintProtocol::default(), START);
+        let mut world = HashMap::<RobotCoord, Color>::new();
+
+        assert_eq!(robot.step(&mut world, ()),
+            Err(StepError::PartialAnswer { outputs: vec![1], expected: 2 }));
+        assert_eq!(robot.get_pose(), START);
+        assert!(world.is_empty());
+    }
+
+    #[test]
+    fn test_move_protocol() {
+        let cpu = ScriptedMachine::new(vec![vec![0], vec![1]]);
+        let mut robot = GridRobot::new(cpu, MoveProtocol::default(), START);
+        let mut world = HashMap::new();
+
+        let outcome = robot.step(&mut world, Dir::Right).unwrap();
+        assert_eq!(outcome, Moved { pos: RobotCoord { x: 1, y: 0 }, cell: Status::Wall, moved: false });
+        assert_eq!(robot.get_pos(), START.pos);
+        assert_eq!(robot.get_dir(), Dir::Right);
+
+        let outcome = robot.step(&mut world, Dir::Down).unwrap();
+        assert!(outcome.moved);
+        assert_eq!(robot.get_pos(), RobotCoord { x: 0, y: 1 });
+        assert_eq!(robot.cpu.inputs, vec![4, 2]);
+        assert_eq!(world.get_cell(RobotCoord { x: 1, y: 0 }), Status::Wall);
+
+        let fork = robot.fork();
+        assert_eq!(fork.get_pose(), robot.get_pose());
+        assert_eq!(fork.get_history(), &[robot.get_pose()]);
+        assert_eq!(robot.get_history().len(), 3);
+    }
+}
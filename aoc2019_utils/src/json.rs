@@ -0,0 +1,147 @@
+use std::fmt;
+
+/// Just enough JSON to write results out. Objects keep their fields in the
+/// order they were given, so output is stable from run to run.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Self {
+        Self::Object(fields.into_iter()
+            .map(|(key, val)| (key.to_string(), val))
+            .collect())
+    }
+}
+
+macro_rules! impl_json_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(num: $t) -> Self {
+                    Self::Integer(num as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_json_from_int!(i32, i64, u32, u64, usize);
+
+impl From<bool> for Json {
+    fn from(val: bool) -> Self {
+        Self::Bool(val)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(num: f64) -> Self {
+        Self::Float(num)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(txt: &str) -> Self {
+        Self::Str(txt.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(txt: String) -> Self {
+        Self::Str(txt)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(val: Option<T>) -> Self {
+        val.map(|val| val.into()).unwrap_or(Self::Null)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(vals: Vec<T>) -> Self {
+        Self::Array(vals.into_iter().map(|val| val.into()).collect())
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter, txt: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in txt.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(val) => write!(f, "{}", val),
+            Self::Integer(num) => write!(f, "{}", num),
+            // JSON has no NaN or infinity.
+            Self::Float(num) if !num.is_finite() => write!(f, "null"),
+            Self::Float(num) => write!(f, "{}", num),
+            Self::Str(txt) => write_str(f, txt),
+            Self::Array(vals) => {
+                write!(f, "[")?;
+                for (i, val) in vals.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", val)?;
+                }
+                write!(f, "]")
+            },
+            Self::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, val)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", val)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let json = Json::object(vec![
+            ("day", 8.into()),
+            ("text", "say \"hi\"\n\\".into()),
+            ("rows", vec!["#.", ".#"].into()),
+            ("time", 1.5.into()),
+            ("ocr", None::<String>.into()),
+            ("ok", true.into()),
+            ("nan", f64::NAN.into()),
+        ]);
+        assert_eq!(json.to_string(), concat!(
+            r##"{"day":8,"text":"say \"hi\"\n\\","rows":["#.",".#"],"##,
+            r##""time":1.5,"ocr":null,"ok":true,"nan":null}"##,
+        ));
+        assert_eq!(Json::from("\u{1}").to_string(), r#""\u0001""#);
+        assert_eq!(Json::Array(vec![]).to_string(), "[]");
+    }
+}
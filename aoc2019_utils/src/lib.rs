@@ -1,4 +1,11 @@
+pub mod grid;
+pub mod grid_robot;
+pub mod json;
+pub mod ocr;
+pub mod permutations;
 pub mod point_2d;
+pub mod search;
+pub mod solution;
 pub mod vec3d;
 
 use std::fs;
@@ -11,6 +18,36 @@ pub fn get_input(filename: &str) -> String {
     fs::read_to_string(filename).expect(&err_msg)
 }
 
+pub fn write_file(filename: &str, contents: &str) {
+    let err_msg = format!("Something went wrong writing the file: {}", filename);
+    fs::write(filename, contents).expect(&err_msg);
+    println!("wrote {}", filename);
+}
+
+/// Goes through the command line flags, handing each one to `handle_arg`
+/// along with a closure that takes the value after it. `handle_arg` returns
+/// false for flags it doesn't know, which panics with `usage`.
+pub fn parse_args<F>(usage: &str, handle_arg: F)
+where
+    F: FnMut(&str, &mut dyn FnMut() -> String) -> bool,
+{
+    parse_arg_list(std::env::args().skip(1), usage, handle_arg)
+}
+
+fn parse_arg_list<I, F>(args: I, usage: &str, mut handle_arg: F)
+where
+    I: Iterator<Item = String>,
+    F: FnMut(&str, &mut dyn FnMut() -> String) -> bool,
+{
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut next_arg = || args.next().unwrap_or_else(|| panic!("{} needs a value", arg));
+        if !handle_arg(&arg, &mut next_arg) {
+            panic!("unknown arg: {} (expected {})", arg, usage);
+        }
+    }
+}
+
 pub fn fact(n: u64) -> u64 {
     let mut n = n;
     let mut ans = 1;
@@ -32,6 +69,10 @@ pub fn gcd(a: u64, b: u64) -> u64 {
     a
 }
 
+pub fn lcm(a: u64, b: u64) -> u64 {
+    a * b / gcd(a, b)
+}
+
 pub fn gcd_extended(a: i64, b: i64) -> (i64, i64, i64) {
     let mut s = 0i64;
     let mut old_s = 1i64;
@@ -94,6 +135,38 @@ mod tests {
         assert_eq!(fact(6), 720);
     }
 
+    #[test]
+    fn test_parse_arg_list() {
+        let args = ["--out", "a.txt", "--verbose", "--out", "b.txt"];
+        let mut files = vec![];
+        let mut verbose = false;
+        parse_arg_list(args.iter().map(|arg| arg.to_string()), "", |arg, next_arg| {
+            match arg {
+                "--out" => files.push(next_arg()),
+                "--verbose" => verbose = true,
+                _ => return false,
+            }
+            true
+        });
+        assert_eq!(files, vec!["a.txt", "b.txt"]);
+        assert!(verbose);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown arg: --bad (expected --out <file>)")]
+    fn test_parse_arg_list_unknown() {
+        parse_arg_list(std::iter::once("--bad".to_string()), "--out <file>", |_, _| false);
+    }
+
+    #[test]
+    #[should_panic(expected = "--out needs a value")]
+    fn test_parse_arg_list_missing_value() {
+        parse_arg_list(std::iter::once("--out".to_string()), "--out <file>", |_, next_arg| {
+            next_arg();
+            true
+        });
+    }
+
     #[test]
     fn test_gcd() {
         assert_eq!(gcd(0, 0), 0);
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::grid::Grid;
+
+pub const GLYPH_HEIGHT: usize = 6;
+
+// The font the puzzles draw their letters in, trimmed to the lit columns.
+// Letters are normally 4 wide plus a blank spacer column, but Y fills the
+// spacer, so it can touch the letter after it.
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const UNKNOWN_CHAR: char = '?';
+
+/// A glyph that isn't in the font, drawn with `#` and `.`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnknownGlyph {
+    /// Where the glyph is in the text, counting from 0.
+    pub index: usize,
+    pub rows: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OcrError {
+    Blank,
+    /// `text` has a `?` in place of each unknown glyph.
+    UnknownGlyphs { text: String, glyphs: Vec<UnknownGlyph> },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Blank => write!(f, "there's nothing to read"),
+            Self::UnknownGlyphs { text, glyphs } => {
+                write!(f, "read \"{}\" with {} unknown glyph(s):", text, glyphs.len())?;
+                for glyph in glyphs {
+                    write!(f, "\nglyph {}:", glyph.index)?;
+                    for row in &glyph.rows {
+                        write!(f, "\n  {}", row)?;
+                    }
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// The lit part of an image, as rows of pixels.
+struct Cropped {
+    rows: Vec<Vec<bool>>,
+    width: usize,
+}
+
+impl Cropped {
+    fn new(pixels: &Grid<bool>) -> Option<Self> {
+        let lit = pixels.iter()
+            .filter(|(_, on)| **on)
+            .map(|(coord, _)| coord)
+            .collect::<Vec<_>>();
+        let min_x = lit.iter().map(|coord| coord.x).min()?;
+        let max_x = lit.iter().map(|coord| coord.x).max()?;
+        let min_y = lit.iter().map(|coord| coord.y).min()?;
+        let max_y = lit.iter().map(|coord| coord.y).max()?;
+
+        let rows = (min_y..=max_y)
+            .map(|y| (min_x..=max_x).map(|x| pixels[(x, y).into()]).collect())
+            .collect();
+        Some(Self { rows, width: (max_x - min_x + 1) as usize })
+    }
+
+    fn is_blank_column(&self, x: usize) -> bool {
+        self.rows.iter().all(|row| !row[x])
+    }
+
+    fn matches(&self, left: usize, glyph: &[&str]) -> bool {
+        let glyph_width = glyph[0].len();
+        left + glyph_width <= self.width
+            && self.rows.len() == glyph.len()
+            && self.rows.iter().zip(glyph).all(|(row, glyph_row)| {
+                row[left..(left + glyph_width)].iter()
+                    .zip(glyph_row.bytes())
+                    .all(|(on, c)| *on == (c == b'#'))
+            })
+    }
+
+    fn render_columns(&self, left: usize, right: usize) -> Vec<String> {
+        self.rows.iter()
+            .map(|row| row[left..right].iter().map(|on| if *on { '#' } else { '.' }).collect())
+            .collect()
+    }
+}
+
+/// Reads the letters drawn in an image, where `true` pixels are lit. Glyphs
+/// are told apart by the blank columns between them, so the letters can be
+/// anywhere in the image.
+pub fn read_text(pixels: &Grid<bool>) -> Result<String, OcrError> {
+    let image = Cropped::new(pixels).ok_or(OcrError::Blank)?;
+
+    let mut text = String::new();
+    let mut unknown = vec![];
+    let mut x = 0;
+    while x < image.width {
+        if image.is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let known = FONT.iter()
+            .filter(|(_, glyph)| image.matches(x, glyph))
+            .max_by_key(|(_, glyph)| glyph[0].len());
+        match known {
+            Some((c, glyph)) => {
+                text.push(*c);
+                x += glyph[0].len();
+            },
+            None => {
+                let right = (x..image.width)
+                    .find(|col| image.is_blank_column(*col))
+                    .unwrap_or(image.width);
+                unknown.push(UnknownGlyph {
+                    index: text.chars().count(),
+                    rows: image.render_columns(x, right),
+                });
+                text.push(UNKNOWN_CHAR);
+                x = right;
+            },
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs { text, glyphs: unknown })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_image(rows: &[&str]) -> Grid<bool> {
+        Grid::parse(&rows.join("\n"), |c| match c {
+            '#' => Some(true),
+            ' ' | '.' => Some(false),
+            _ => None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_read_text() {
+        let image = make_image(&[
+            "                ",
+            "  ##  ###  #  # ",
+            " #  # #  # #  # ",
+            " #  # #  # #  # ",
+            " #### ###  #  # ",
+            " #  # #    #  # ",
+            " #  # #     ##  ",
+        ]);
+        assert_eq!(read_text(&image), Ok("APU".to_string()));
+    }
+
+    #[test]
+    fn test_read_text_narrow_and_touching() {
+        // I is only 3 wide, and Y runs right into the A after it.
+        let image = make_image(&[
+            "### #...#.##.",
+            ".#. #...##..#",
+            ".#. .#.#.#..#",
+            ".#. ..#..####",
+            ".#. ..#..#..#",
+            "### ..#..#..#",
+        ]);
+        assert_eq!(read_text(&image), Ok("IYA".to_string()));
+    }
+
+    #[test]
+    fn test_read_text_unknown() {
+        let image = make_image(&[
+            "#### ##",
+            "#    ##",
+            "###  ##",
+            "#    ##",
+            "#    ##",
+            "#    ##",
+        ]);
+        let err = read_text(&image).unwrap_err();
+        assert_eq!(err, OcrError::UnknownGlyphs {
+            text: "F?".to_string(),
+            glyphs: vec![UnknownGlyph { index: 1, rows: vec!["##".to_string(); 6] }],
+        });
+        assert_eq!(err.to_string(), concat!(
+            "read \"F?\" with 1 unknown glyph(s):\n",
+            "glyph 1:\n  ##\n  ##\n  ##\n  ##\n  ##\n  ##",
+        ));
+
+        assert_eq!(read_text(&make_image(&["    ", "    "])), Err(OcrError::Blank));
+    }
+}
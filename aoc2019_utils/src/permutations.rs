@@ -0,0 +1,77 @@
+/// Every ordering of some items, made one at a time with Heap's algorithm, so
+/// there's never more than one in memory. Each ordering is one swap away from
+/// the one before, rather than in lexicographic order.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    idx: usize,
+    started: bool,
+}
+
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        idx: 1,
+        started: false,
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if !self.started {
+            self.started = true;
+            return Some(self.items.clone());
+        }
+
+        // `counters` is the loop state of the recursive form of the
+        // algorithm, kept between calls.
+        while self.idx < self.items.len() {
+            let idx = self.idx;
+            if self.counters[idx] < idx {
+                let other = if idx.is_multiple_of(2) { 0 } else { self.counters[idx] };
+                self.items.swap(other, idx);
+                self.counters[idx] += 1;
+                self.idx = 1;
+                return Some(self.items.clone());
+            }
+            self.counters[idx] = 0;
+            self.idx += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::fact;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(&[1, 2, 3]).collect::<Vec<Vec<i32>>>(), vec![
+            vec![1, 2, 3],
+            vec![2, 1, 3],
+            vec![3, 1, 2],
+            vec![1, 3, 2],
+            vec![2, 3, 1],
+            vec![3, 2, 1],
+        ]);
+        assert_eq!(permutations::<i32>(&[]).collect::<Vec<Vec<i32>>>(), vec![vec![]]);
+
+        for n in 1..=7 {
+            let items = (0..n).collect::<Vec<u64>>();
+            let all = permutations(&items).collect::<Vec<Vec<u64>>>();
+            assert_eq!(all.len() as u64, fact(n));
+            assert_eq!(all.iter().collect::<HashSet<_>>().len(), all.len());
+            assert!(all.windows(2).all(|pair| {
+                pair[0].iter().zip(&pair[1]).filter(|(a, b)| a != b).count() == 2
+            }));
+        }
+    }
+}
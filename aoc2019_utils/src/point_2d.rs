@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::ops::Neg;
 use std::ops::Add;
 use std::ops::AddAssign;
@@ -20,6 +22,223 @@ impl<T: PartialEq + Eq + Clone + Copy> Point2d<T> {
     }
 }
 
+/// The integer types a `Point2d` can step around the grid with.
+pub trait CoordNum: Copy + Eq + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coord_num {
+    ($($t:ty),*) => {
+        $(
+            impl CoordNum for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord_num!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+fn abs_diff<T: CoordNum>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T: CoordNum> Point2d<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The number of king's moves between the points.
+    pub fn chebyshev(self, other: Self) -> T {
+        std::cmp::max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+
+    /// The neighbouring point in `dir`, or `None` if it can't be represented
+    /// (e.g. stepping left from x = 0 with an unsigned type).
+    pub fn step(self, dir: Direction) -> Option<Self> {
+        let (x, y) = match dir {
+            Direction::Up => (Some(self.x), self.y.checked_sub(T::ONE)),
+            Direction::Down => (Some(self.x), self.y.checked_add(T::ONE)),
+            Direction::Left => (self.x.checked_sub(T::ONE), Some(self.y)),
+            Direction::Right => (self.x.checked_add(T::ONE), Some(self.y)),
+        };
+        Some(Self { x: x?, y: y? })
+    }
+
+    /// The up to 4 orthogonal neighbours, in reading order.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::READING_ORDER.iter().filter_map(move |dir| self.step(*dir))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours, in reading order.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        let above = self.step(Direction::Up);
+        let below = self.step(Direction::Down);
+        let rows = vec![above, Some(self), below];
+        rows.into_iter().flatten().flat_map(move |row| {
+            let is_self_row = row == self;
+            vec![row.step(Direction::Left), Some(row), row.step(Direction::Right)]
+                .into_iter()
+                .flatten()
+                .filter(move |point| !is_self_row || *point != self)
+        })
+    }
+
+    /// Converts to another integer type that holds every value of this one.
+    pub fn cast<U>(self) -> Point2d<U>
+    where
+        U: CoordNum + From<T>,
+    {
+        Point2d { x: U::from(self.x), y: U::from(self.y) }
+    }
+
+    /// Converts to another integer type, or `None` if either coordinate is
+    /// out of its range.
+    pub fn try_cast<U>(self) -> Option<Point2d<U>>
+    where
+        U: CoordNum + TryFrom<T>,
+    {
+        Some(Point2d { x: U::try_from(self.x).ok()?, y: U::try_from(self.y).ok()? })
+    }
+}
+
+impl<T> Point2d<T>
+where
+    T: CoordNum + Neg<Output = T>,
+{
+    /// Rotates 90 degrees clockwise about the origin, with y growing downward
+    /// (so up turns into right).
+    pub fn rotate_cw(self) -> Self {
+        Self { x: -self.y, y: self.x }
+    }
+
+    /// Rotates 90 degrees counterclockwise about the origin, with y growing
+    /// downward (so up turns into left).
+    pub fn rotate_ccw(self) -> Self {
+        Self { x: self.y, y: -self.x }
+    }
+}
+
+impl<T: PartialEq + Eq + Clone + Copy> From<(T, T)> for Point2d<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: PartialEq + Eq + Clone + Copy> From<Point2d<T>> for (T, T) {
+    fn from(point: Point2d<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// Points sort in reading order: top to bottom, then left to right.
+impl<T: Ord + Copy> Ord for Point2d<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl<T: Ord + Copy> PartialOrd for Point2d<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Turn { Left, Right }
+
+/// A heading on the grid. Up is toward negative y, matching the way every
+/// puzzle prints its grid.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Direction { Up, Down, Left, Right }
+
+impl Direction {
+    pub const ALL: [Direction; 4] =
+        [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// The order neighbours come in when read like text.
+    pub const READING_ORDER: [Direction; 4] =
+        [Direction::Up, Direction::Left, Direction::Right, Direction::Down];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        match self {
+            Self::Up | Self::Down => true,
+            Self::Left | Self::Right => false,
+        }
+    }
+
+    pub fn is_parallel_to(self, other: Direction) -> bool {
+        self.is_vertical() == other.is_vertical()
+    }
+
+    /// The unit step in this direction.
+    pub fn offset<T>(self) -> Point2d<T>
+    where
+        T: CoordNum + Neg<Output = T>,
+    {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Self::Up => Point2d { x: zero, y: -one },
+            Self::Down => Point2d { x: zero, y: one },
+            Self::Left => Point2d { x: -one, y: zero },
+            Self::Right => Point2d { x: one, y: zero },
+        }
+    }
+
+    pub fn move_coord<T>(self, coord: Point2d<T>) -> Point2d<T>
+    where
+        T: CoordNum + Neg<Output = T>,
+    {
+        coord + self.offset()
+    }
+}
+
 impl<T> Neg for Point2d<T>
 where
     T: Neg<Output = T>
@@ -183,6 +402,84 @@ mod tests {
         assert_eq!(Point2d::new(5, 7), Point2d { x: 5, y: 7 });
     }
 
+    #[test]
+    fn test_distances() {
+        let a = Point2d { x: 1, y: -2 };
+        let b = Point2d { x: -3, y: 5 };
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Point2d::<u8> { x: 2, y: 9 }.manhattan(Point2d { x: 5, y: 1 }), 11);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let up = Direction::Up.offset::<i32>();
+        assert_eq!(up.rotate_cw(), Direction::Right.offset());
+        assert_eq!(up.rotate_ccw(), Direction::Left.offset());
+        let p = Point2d { x: 3, y: -1 };
+        assert_eq!(p.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), p);
+        assert_eq!(p.rotate_cw().rotate_ccw(), p);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point2d { x: 5i32, y: 5 };
+        assert_eq!(p.neighbours4().collect::<Vec<_>>(), vec![
+            Point2d { x: 5, y: 4 },
+            Point2d { x: 4, y: 5 },
+            Point2d { x: 6, y: 5 },
+            Point2d { x: 5, y: 6 },
+        ]);
+        let neighbours8 = p.neighbours8().collect::<Vec<_>>();
+        assert_eq!(neighbours8.len(), 8);
+        assert!(neighbours8.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let corner = Point2d { x: 0u8, y: 0 };
+        assert_eq!(corner.neighbours4().count(), 2);
+        assert_eq!(corner.neighbours8().count(), 3);
+        assert_eq!(corner.step(Direction::Left), None);
+    }
+
+    #[test]
+    fn test_direction() {
+        Direction::ALL.iter().for_each(|dir| {
+            assert_eq!(dir.turn_left().turn_right(), *dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.offset::<i64>().rotate_cw(), dir.turn_right().offset());
+            assert_eq!(Point2d { x: 1i16, y: 1 }.step(*dir), Some(dir.move_coord(Point2d { x: 1, y: 1 })));
+        });
+        assert!(Direction::Up.is_parallel_to(Direction::Down));
+        assert!(!Direction::Up.is_parallel_to(Direction::Left));
+    }
+
+    #[test]
+    fn test_casts() {
+        let p = Point2d { x: 200u8, y: 3 };
+        assert_eq!(p.cast::<i32>(), Point2d { x: 200, y: 3 });
+        assert_eq!(p.try_cast::<i8>(), None);
+        assert_eq!(Point2d { x: -1i32, y: 4 }.try_cast::<u8>(), None);
+        assert_eq!(Point2d { x: 1i32, y: 4 }.try_cast::<u8>(), Some(Point2d { x: 1, y: 4 }));
+        assert_eq!(Point2d::from((2, 3)), Point2d { x: 2, y: 3 });
+        assert_eq!(<(i32, i32)>::from(Point2d { x: 2, y: 3 }), (2, 3));
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let mut points = vec![
+            Point2d { x: 2, y: 1 },
+            Point2d { x: 0, y: 2 },
+            Point2d { x: 1, y: 1 },
+            Point2d { x: 5, y: 0 },
+        ];
+        points.sort();
+        assert_eq!(points, vec![
+            Point2d { x: 5, y: 0 },
+            Point2d { x: 1, y: 1 },
+            Point2d { x: 2, y: 1 },
+            Point2d { x: 0, y: 2 },
+        ]);
+    }
+
     #[test]
     fn test_neg() {
         assert_eq!(
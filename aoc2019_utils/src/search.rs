@@ -0,0 +1,388 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Anything that can be searched: a set of states and the edges leading out
+/// of each one.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The states one move away from `node`, each with the cost of the move.
+    /// BFS and flood fill treat every move as costing 1.
+    fn edges(&self, node: &Self::Node) -> Vec<(Self::Node, u32)>;
+}
+
+/// A graph whose edges come from a closure, for searches that don't warrant
+/// their own type.
+pub struct FnGraph<N, F> {
+    get_edges: F,
+    _node: PhantomData<N>,
+}
+
+impl<N, F> Graph for FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u32)>,
+{
+    type Node = N;
+
+    fn edges(&self, node: &N) -> Vec<(N, u32)> {
+        (self.get_edges)(node)
+    }
+}
+
+pub fn graph_from_fn<N, F>(get_edges: F) -> FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u32)>,
+{
+    FnGraph { get_edges, _node: PhantomData }
+}
+
+/// A graph whose moves all cost 1, built from a closure that lists a state's
+/// neighbours.
+pub fn unit_graph_from_fn<N, F>(get_neighbours: F)
+-> FnGraph<N, impl Fn(&N) -> Vec<(N, u32)>>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<N>,
+{
+    graph_from_fn(move |node: &N| {
+        get_neighbours(node).into_iter().map(|next| (next, 1)).collect()
+    })
+}
+
+/// The cheapest known way to reach every state a search visited, stored as
+/// a parent link per state.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SearchTree<N: Eq + Hash> {
+    pub root: N,
+    parents: HashMap<N, N>,
+    costs: HashMap<N, u32>,
+}
+
+impl<N: Clone + Eq + Hash> SearchTree<N> {
+    fn new(root: N) -> Self {
+        let mut costs = HashMap::new();
+        costs.insert(root.clone(), 0);
+        Self { root, parents: HashMap::new(), costs }
+    }
+
+    fn set(&mut self, node: N, parent: N, cost: u32) {
+        self.parents.insert(node.clone(), parent);
+        self.costs.insert(node, cost);
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    pub fn get_cost(&self, node: &N) -> Option<u32> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn get_max_cost(&self) -> u32 {
+        self.costs.values().copied().max().unwrap_or(0)
+    }
+
+    /// Every state in the tree with the cost of reaching it, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, u32)> {
+        self.costs.iter().map(|(node, cost)| (node, *cost))
+    }
+
+    /// The states from the root to `node`, both included.
+    pub fn get_path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut at = node;
+        while let Some(parent) = self.parents.get(at) {
+            path.push(parent.clone());
+            at = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A path found by a search, from the start to the goal (both included).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SearchPath<N> {
+    pub path: Vec<N>,
+    pub cost: u32,
+}
+
+impl<N> SearchPath<N> {
+    pub fn get_goal(&self) -> &N {
+        self.path.last().expect("a search path always has its start")
+    }
+
+    /// The number of moves along the path.
+    pub fn num_moves(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+fn to_search_path<N: Clone + Eq + Hash>(tree: &SearchTree<N>, goal: &N) -> SearchPath<N> {
+    SearchPath {
+        path: tree.get_path_to(goal).expect("the goal was never reached"),
+        cost: tree.get_cost(goal).expect("the goal was never reached"),
+    }
+}
+
+// Breadth first from `start`, stopping early at the first state matching
+// `is_goal`.
+fn run_bfs<G, P>(graph: &G, start: G::Node, mut is_goal: P)
+-> (SearchTree<G::Node>, Option<G::Node>)
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+{
+    let mut tree = SearchTree::new(start.clone());
+    if is_goal(&start) {
+        return (tree, Some(start));
+    }
+
+    let mut to_visit = VecDeque::new();
+    to_visit.push_back(start);
+
+    while let Some(at) = to_visit.pop_front() {
+        let cost = tree.costs[&at] + 1;
+        for (next, _) in graph.edges(&at) {
+            if tree.contains(&next) {
+                continue;
+            }
+            tree.set(next.clone(), at.clone(), cost);
+            if is_goal(&next) {
+                return (tree, Some(next));
+            }
+            to_visit.push_back(next);
+        }
+    }
+
+    (tree, None)
+}
+
+// Best first from `start` ordered by cost plus `heuristic`, stopping early at
+// the first state matching `is_goal` to be taken off the queue.
+fn run_astar<G, P, H>(graph: &G, start: G::Node, mut is_goal: P, heuristic: H)
+-> (SearchTree<G::Node>, Option<G::Node>)
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+    H: Fn(&G::Node) -> u32,
+{
+    let mut tree = SearchTree::new(start.clone());
+
+    // The heap holds indexes into `nodes` so states don't need to be Ord.
+    // Stale entries (ones that were beaten after being pushed) are skipped
+    // when they come off.
+    let mut nodes = vec![start.clone()];
+    let mut to_visit = BinaryHeap::new();
+    to_visit.push(Reverse((heuristic(&start), 0, 0)));
+
+    while let Some(Reverse((_, cost, idx))) = to_visit.pop() {
+        let at = nodes[idx].clone();
+        if tree.costs[&at] < cost {
+            continue;
+        }
+        if is_goal(&at) {
+            return (tree, Some(at));
+        }
+
+        for (next, edge_cost) in graph.edges(&at) {
+            let next_cost = cost + edge_cost;
+            if matches!(tree.get_cost(&next), Some(known) if known <= next_cost) {
+                continue;
+            }
+            tree.set(next.clone(), at.clone(), next_cost);
+            to_visit.push(Reverse((next_cost + heuristic(&next), next_cost, nodes.len())));
+            nodes.push(next);
+        }
+    }
+
+    (tree, None)
+}
+
+/// The path with the fewest moves from `start` to any state matching
+/// `is_goal`. Edge costs are ignored.
+pub fn bfs<G, P>(graph: &G, start: G::Node, is_goal: P) -> Option<SearchPath<G::Node>>
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+{
+    let (tree, goal) = run_bfs(graph, start, is_goal);
+    goal.map(|goal| to_search_path(&tree, &goal))
+}
+
+/// The cheapest path from `start` to any state matching `is_goal`.
+pub fn dijkstra<G, P>(graph: &G, start: G::Node, is_goal: P) -> Option<SearchPath<G::Node>>
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+{
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// The cheapest path from `start` to any state matching `is_goal`, guided by
+/// `heuristic`. The heuristic must never overestimate the remaining cost or
+/// the path found may not be the cheapest.
+pub fn astar<G, P, H>(graph: &G, start: G::Node, is_goal: P, heuristic: H)
+-> Option<SearchPath<G::Node>>
+where
+    G: Graph,
+    P: FnMut(&G::Node) -> bool,
+    H: Fn(&G::Node) -> u32,
+{
+    let (tree, goal) = run_astar(graph, start, is_goal, heuristic);
+    goal.map(|goal| to_search_path(&tree, &goal))
+}
+
+/// The fewest moves from `start` to every reachable state. Edge costs are
+/// ignored.
+pub fn flood_fill<G: Graph>(graph: &G, start: G::Node) -> SearchTree<G::Node> {
+    run_bfs(graph, start, |_| false).0
+}
+
+/// The cheapest cost from `start` to every reachable state.
+pub fn dijkstra_all<G: Graph>(graph: &G, start: G::Node) -> SearchTree<G::Node> {
+    run_astar(graph, start, |_| false, |_| 0).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::point_2d::Point2d;
+
+    type Coord = Point2d<i32>;
+
+    // '#' is a wall, digits cost that much to step onto, anything else costs 1
+    struct Maze {
+        rows: Vec<Vec<char>>,
+    }
+
+    impl Maze {
+        fn new(rows: &[&str]) -> Self {
+            Self { rows: rows.iter().map(|row| row.chars().collect()).collect() }
+        }
+
+        fn get(&self, coord: Coord) -> char {
+            if coord.x < 0 || coord.y < 0 {
+                return '#';
+            }
+            self.rows.get(coord.y as usize)
+                .and_then(|row| row.get(coord.x as usize))
+                .copied()
+                .unwrap_or('#')
+        }
+
+        fn find(&self, c: char) -> Coord {
+            self.rows.iter().enumerate()
+                .find_map(|(y, row)| {
+                    row.iter().position(|cell| *cell == c)
+                        .map(|x| Coord { x: x as i32, y: y as i32 })
+                })
+                .unwrap()
+        }
+    }
+
+    impl Graph for Maze {
+        type Node = Coord;
+
+        fn edges(&self, node: &Coord) -> Vec<(Coord, u32)> {
+            [(0, -1), (-1, 0), (1, 0), (0, 1)].iter()
+                .map(|(dx, dy)| Coord { x: node.x + dx, y: node.y + dy })
+                .filter(|next| self.get(*next) != '#')
+                .map(|next| (next, self.get(next).to_digit(10).unwrap_or(1)))
+                .collect()
+        }
+    }
+
+    fn example_maze() -> Maze {
+        Maze::new(&[
+            "#######",
+            "#S..9E#",
+            "#.###.#",
+            "#.....#",
+            "#######",
+        ])
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = example_maze();
+        let (start, end) = (maze.find('S'), maze.find('E'));
+        let result = bfs(&maze, start, |node| *node == end).unwrap();
+        assert_eq!(result.num_moves(), 4);
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.path[0], start);
+        assert_eq!(*result.get_goal(), end);
+
+        assert_eq!(bfs(&maze, start, |node| *node == start).unwrap().path, vec![start]);
+        assert_eq!(bfs(&maze, start, |node| node.x > 10), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let maze = example_maze();
+        let (start, end) = (maze.find('S'), maze.find('E'));
+        let result = dijkstra(&maze, start, |node| *node == end).unwrap();
+        // going around the bottom beats stepping on the 9
+        assert_eq!(result.cost, 8);
+        assert_eq!(result.num_moves(), 8);
+        assert!(result.path.iter().all(|node| maze.get(*node) != '9'));
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = example_maze();
+        let (start, end) = (maze.find('S'), maze.find('E'));
+        let result = astar(&maze, start, |node| *node == end, |node| node.manhattan(end) as u32);
+        assert_eq!(result, dijkstra(&maze, start, |node| *node == end));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let maze = example_maze();
+        let start = maze.find('S');
+        let tree = flood_fill(&maze, start);
+        assert_eq!(tree.len(), 12);
+        assert_eq!(tree.get_max_cost(), 6);
+        assert_eq!(tree.get_cost(&Coord { x: 0, y: 0 }), None);
+        assert_eq!(tree.get_path_to(&Coord { x: 1, y: 3 }), Some(vec![
+            start,
+            Coord { x: 1, y: 2 },
+            Coord { x: 1, y: 3 },
+        ]));
+
+        let tree = dijkstra_all(&maze, start);
+        assert_eq!(tree.get_cost(&maze.find('9')), Some(11));
+        assert_eq!(tree.get_cost(&maze.find('E')), Some(8));
+    }
+
+    #[test]
+    fn test_fn_graph() {
+        // count up by 1 or double, from 1 to 10
+        let graph = unit_graph_from_fn(|n: &u32| vec![n + 1, n * 2]);
+        let result = bfs(&graph, 1, |n| *n == 10).unwrap();
+        assert_eq!(result.path, vec![1, 2, 4, 5, 10]);
+
+        let graph = graph_from_fn(|n: &u32| vec![(n + 1, 1), (n * 2, 5)]);
+        assert_eq!(dijkstra(&graph, 1, |n| *n == 10).unwrap().cost, 9);
+    }
+}
@@ -0,0 +1,274 @@
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::json::Json;
+
+const PIXEL_ON: char = '#';
+const PIXEL_OFF: char = ' ';
+
+/// What a puzzle part produces. Some puzzles draw their answer, so an image
+/// keeps the pixels along with the text read from them, if it could be read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    Image { pixels: Grid<bool>, text: Option<String> },
+}
+
+impl Answer {
+    pub fn image(pixels: Grid<bool>, text: Option<String>) -> Self {
+        Self::Image { pixels, text }
+    }
+
+    pub fn render_pixels(pixels: &Grid<bool>) -> String {
+        pixels.render(|on| if *on { PIXEL_ON } else { PIXEL_OFF })
+    }
+
+    pub fn to_json(&self) -> Json {
+        match self {
+            Self::Integer(num) => Json::object(vec![
+                ("type", "integer".into()),
+                ("value", (*num).into()),
+            ]),
+            Self::Text(txt) => Json::object(vec![
+                ("type", "text".into()),
+                ("value", txt.as_str().into()),
+            ]),
+            Self::Image { pixels, text } => Json::object(vec![
+                ("type", "image".into()),
+                ("width", pixels.width().into()),
+                ("height", pixels.height().into()),
+                ("rows", Self::render_pixels(pixels).lines().collect::<Vec<&str>>().into()),
+                ("text", text.clone().into()),
+            ]),
+        }
+    }
+}
+
+/// Images show their text when it could be read and their pixels otherwise.
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(num) => write!(f, "{}", num),
+            Self::Text(txt) => write!(f, "{}", txt),
+            Self::Image { text: Some(txt), .. } => write!(f, "{}", txt),
+            Self::Image { pixels, text: None } => {
+                write!(f, "{}", Self::render_pixels(pixels))
+            },
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Self {
+                    Self::Integer(num as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(txt: String) -> Self {
+        Self::Text(txt)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(txt: &str) -> Self {
+        Self::Text(txt.to_string())
+    }
+}
+
+impl From<Grid<bool>> for Answer {
+    fn from(pixels: Grid<bool>) -> Self {
+        Self::image(pixels, None)
+    }
+}
+
+/// A way to give `None` results an answer to show.
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map(|answer| answer.into()).unwrap_or_else(|| "no solution".into())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Part { A, B }
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::A, Part::B];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(txt: &str) -> Result<Self, Self::Err> {
+        match txt {
+            "a" | "A" => Ok(Self::A),
+            "b" | "B" => Ok(Self::B),
+            _ => Err(format!("bad part: {} (expected a or b)", txt)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A => write!(f, "a"),
+            Self::B => write!(f, "b"),
+        }
+    }
+}
+
+/// One day's puzzle. The input is parsed once and both parts work from the
+/// parsed form, so each stage can be run and timed on its own. `part_a` and
+/// `part_b` do both steps for callers that just have the input text.
+pub trait Solution {
+    type Parsed;
+
+    fn get_day(&self) -> u32;
+    fn parse(&self, input: &str) -> Self::Parsed;
+    fn solve_a(&self, parsed: &Self::Parsed) -> Answer;
+    fn solve_b(&self, parsed: &Self::Parsed) -> Answer;
+
+    fn part_a(&self, input: &str) -> Answer {
+        self.solve_a(&self.parse(input))
+    }
+
+    fn part_b(&self, input: &str) -> Answer {
+        self.solve_b(&self.parse(input))
+    }
+}
+
+/// A `Solution` with its parsed type hidden, so every day can go in one list.
+/// Every `Solution` is one.
+pub trait AnySolution {
+    fn get_day(&self) -> u32;
+    fn parse_any(&self, input: &str) -> Box<dyn Any>;
+
+    /// Panics if `parsed` didn't come from this solution's `parse_any`.
+    fn solve_parsed(&self, part: Part, parsed: &dyn Any) -> Answer;
+
+    fn solve(&self, part: Part, input: &str) -> Answer {
+        self.solve_parsed(part, self.parse_any(input).as_ref())
+    }
+
+    fn get_input_path(&self) -> String {
+        get_input_path(self.get_day())
+    }
+}
+
+impl<S> AnySolution for S
+where
+    S: Solution,
+    S::Parsed: 'static,
+{
+    fn get_day(&self) -> u32 {
+        Solution::get_day(self)
+    }
+
+    fn parse_any(&self, input: &str) -> Box<dyn Any> {
+        Box::new(self.parse(input))
+    }
+
+    fn solve_parsed(&self, part: Part, parsed: &dyn Any) -> Answer {
+        let parsed = parsed.downcast_ref::<S::Parsed>().unwrap_or_else(|| {
+            panic!("day {} was given another day's input", Solution::get_day(self))
+        });
+        match part {
+            Part::A => self.solve_a(parsed),
+            Part::B => self.solve_b(parsed),
+        }
+    }
+}
+
+/// Where a day's puzzle input lives, relative to the workspace root.
+pub fn get_input_path(day: u32) -> String {
+    format!("inputs/day{:02}.txt", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Parsed = i64;
+
+        fn get_day(&self) -> u32 {
+            7
+        }
+
+        fn parse(&self, input: &str) -> i64 {
+            input.trim().parse().unwrap()
+        }
+
+        fn solve_a(&self, num: &i64) -> Answer {
+            (num * 2).into()
+        }
+
+        fn solve_b(&self, num: &i64) -> Answer {
+            num.to_string().repeat(2).into()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(Doubler.solve(Part::A, "21\n"), Answer::Integer(42));
+        assert_eq!(Doubler.solve(Part::B, "21\n"), Answer::Text("2121".to_string()));
+        assert_eq!(Doubler.get_input_path(), "inputs/day07.txt");
+
+        assert_eq!(Doubler.part_a("21\n"), Answer::Integer(42));
+        assert_eq!(Doubler.part_b("21\n"), Answer::Text("2121".to_string()));
+
+        let parsed = Doubler.parse_any("21\n");
+        assert_eq!(Doubler.solve_parsed(Part::A, parsed.as_ref()), Answer::Integer(42));
+        assert_eq!(Doubler.solve_parsed(Part::B, parsed.as_ref()), Answer::Text("2121".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "day 7 was given another day's input")]
+    fn test_solve_parsed_wrong_type() {
+        Doubler.solve_parsed(Part::A, &"21".to_string());
+    }
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(5u32).to_string(), "5");
+        assert_eq!(Answer::from(Some(-3i32)), Answer::Integer(-3));
+        assert_eq!(Answer::from(None::<u32>).to_string(), "no solution");
+        assert_eq!(Part::from_str("B"), Ok(Part::B));
+        assert!(Part::from_str("c").is_err());
+    }
+
+    #[test]
+    fn test_answer_image() {
+        let pixels = Grid::from_cells(2, vec![false, true, true, false]).unwrap();
+        let answer = Answer::from(pixels.clone());
+        assert_eq!(answer.to_string(), " #\n# \n");
+        assert_eq!(answer.to_json().to_string(),
+            r##"{"type":"image","width":2,"height":2,"rows":[" #","# "],"text":null}"##);
+
+        let answer = Answer::image(pixels.clone(), Some("X".to_string()));
+        assert_eq!(answer.to_string(), "X");
+        assert_ne!(answer, Answer::image(pixels, Some("Y".to_string())));
+        assert_ne!(answer, Answer::Text("X".to_string()));
+    }
+
+    #[test]
+    fn test_answer_to_json() {
+        assert_eq!(Answer::from(-3i32).to_json().to_string(),
+            r#"{"type":"integer","value":-3}"#);
+        assert_eq!(Answer::from("a\"b").to_json().to_string(),
+            r#"{"type":"text","value":"a\"b"}"#);
+    }
+}
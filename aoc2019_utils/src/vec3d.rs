@@ -1,3 +1,5 @@
+use std::ops::Index;
+use std::ops::IndexMut;
 use std::ops::Neg;
 use std::ops::Add;
 use std::ops::AddAssign;
@@ -19,6 +21,121 @@ impl<T: PartialEq + Eq + Clone + Copy> Vec3d<T> {
     pub fn new(x: T, y: T, z: T) -> Self {
         Self { x: x, y: y, z: z }
     }
+
+    pub fn to_array(self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// The components in x, y, z order.
+    pub fn iter(self) -> impl Iterator<Item = T> {
+        Axis::ALL.iter().map(move |axis| self[*axis])
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Vec3d<U>
+    where
+        U: PartialEq + Eq + Clone + Copy,
+        F: FnMut(T) -> U,
+    {
+        Vec3d { x: f(self.x), y: f(self.y), z: f(self.z) }
+    }
+
+    /// Combines the matching components of two vectors.
+    pub fn zip_with<U, V, F>(self, other: Vec3d<U>, mut f: F) -> Vec3d<V>
+    where
+        U: PartialEq + Eq + Clone + Copy,
+        V: PartialEq + Eq + Clone + Copy,
+        F: FnMut(T, U) -> V,
+    {
+        Vec3d { x: f(self.x, other.x), y: f(self.y, other.y), z: f(self.z, other.z) }
+    }
+}
+
+impl<T: Ord + Copy> Vec3d<T> {
+    /// The smaller of each pair of components.
+    pub fn min(self, other: Self) -> Self {
+        self.zip_with(other, std::cmp::min)
+    }
+
+    /// The larger of each pair of components.
+    pub fn max(self, other: Self) -> Self {
+        self.zip_with(other, std::cmp::max)
+    }
+}
+
+/// The signed integer types a `Vec3d` can take the sign and size of.
+pub trait SignedNum: Copy + Eq + Add<Output = Self> {
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_signed_num {
+    ($($t:ty),*) => {
+        $(
+            impl SignedNum for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_num!(i8, i16, i32, i64, isize);
+
+impl<T: SignedNum> Vec3d<T> {
+    /// Each component's sign: -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        self.map(T::signum)
+    }
+
+    pub fn abs(self) -> Self {
+        self.map(T::abs)
+    }
+
+    /// The sum of the components' sizes, i.e. the Manhattan length.
+    pub fn l1_norm(self) -> T {
+        let abs = self.abs();
+        abs.x + abs.y + abs.z
+    }
+}
+
+impl<T: PartialEq + Eq + Clone + Copy> From<[T; 3]> for Vec3d<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Axis { X, Y, Z }
+
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+}
+
+impl<T: PartialEq + Eq + Clone + Copy> Index<Axis> for Vec3d<T> {
+    type Output = T;
+
+    fn index(&self, axis: Axis) -> &Self::Output {
+        match axis {
+            Axis::X => &self.x,
+            Axis::Y => &self.y,
+            Axis::Z => &self.z,
+        }
+    }
+}
+
+impl<T: PartialEq + Eq + Clone + Copy> IndexMut<Axis> for Vec3d<T> {
+    fn index_mut(&mut self, axis: Axis) -> &mut Self::Output {
+        match axis {
+            Axis::X => &mut self.x,
+            Axis::Y => &mut self.y,
+            Axis::Z => &mut self.z,
+        }
+    }
 }
 
 impl<T> Neg for Vec3d<T>
@@ -193,6 +310,42 @@ mod tests {
         assert_eq!(Vec3d::new(5, 7, 9), Vec3d { x: 5, y: 7, z: 9 });
     }
 
+    #[test]
+    fn test_sign_and_size() {
+        let v = Vec3d { x: -4, y: 0, z: 7 };
+        assert_eq!(v.signum(), Vec3d { x: -1, y: 0, z: 1 });
+        assert_eq!(v.abs(), Vec3d { x: 4, y: 0, z: 7 });
+        assert_eq!(v.l1_norm(), 11);
+    }
+
+    #[test]
+    fn test_axis_index() {
+        let mut v = Vec3d { x: 1, y: 2, z: 3 };
+        assert_eq!(Axis::ALL.iter().map(|axis| v[*axis]).collect::<Vec<i32>>(), vec![1, 2, 3]);
+        v[Axis::Y] = 5;
+        assert_eq!(v, Vec3d { x: 1, y: 5, z: 3 });
+    }
+
+    #[test]
+    fn test_min_max() {
+        let a = Vec3d { x: 1, y: 8, z: -3 };
+        let b = Vec3d { x: 4, y: 2, z: -3 };
+        assert_eq!(a.min(b), Vec3d { x: 1, y: 2, z: -3 });
+        assert_eq!(a.max(b), Vec3d { x: 4, y: 8, z: -3 });
+    }
+
+    #[test]
+    fn test_iter_map_zip() {
+        let v = Vec3d::from([1i16, -2, 3]);
+        assert_eq!(v.iter().collect::<Vec<i16>>(), vec![1, -2, 3]);
+        assert_eq!(v.to_array(), [1, -2, 3]);
+        assert_eq!(v.map(|c| c as i64 * 1000), Vec3d { x: 1000, y: -2000, z: 3000 });
+        assert_eq!(
+            v.zip_with(Vec3d { x: true, y: false, z: true }, |c, keep| if keep { c } else { 0 }),
+            Vec3d { x: 1, y: 0, z: 3 },
+        );
+    }
+
     #[test]
     fn test_neg() {
         assert_eq!(